@@ -1,5 +1,22 @@
-use core::ops::{Add, Div, Mul, Sub};
+use crate::num::gcd;
+use crate::parse::{self, ParseError};
+
+use core::convert::{TryFrom, TryInto};
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
 use core::str::FromStr;
+use nom::character::complete::multispace0;
+use nom::sequence::preceded;
+
+pub use crate::num::CheckedArith;
+
+fn abs_diff<T: Copy + Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a < b {
+        b - a
+    } else {
+        a - b
+    }
+}
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Hash)]
 pub struct Point<T> {
@@ -7,31 +24,11 @@ pub struct Point<T> {
     pub y: T,
 }
 
-pub enum ParsePointError<T: FromStr> {
-    InnerType(<T as FromStr>::Err),
-    PointFormat(&'static str),
-}
-
 impl<T: FromStr> FromStr for Point<T> {
-    type Err = ParsePointError<T>;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut part_iter = s
-            .trim()
-            .split(',')
-            .map(|substr| T::from_str(substr.trim()).map_err(ParsePointError::InnerType));
-
-        let x = part_iter
-            .next()
-            .ok_or(ParsePointError::PointFormat("no items in string"))??;
-        let y = part_iter.next().ok_or(ParsePointError::PointFormat(
-            "only 1 item in string; requires 2",
-        ))??;
-
-        match part_iter.next() {
-            None => Ok(Self { x, y }),
-            _ => Err(ParsePointError::PointFormat("more than 2 items in string")),
-        }
+        parse::finish(s, preceded(multispace0, parse::point))
     }
 }
 
@@ -49,6 +46,109 @@ where
     }
 }
 
+impl<T, U> Sub<Vector<U>> for Point<T>
+where
+    T: Sub<U>,
+{
+    type Output = Point<<T as Sub<U>>::Output>;
+
+    fn sub(self, v: Vector<U>) -> Self::Output {
+        Self::Output {
+            x: self.x - v.x,
+            y: self.y - v.y,
+        }
+    }
+}
+
+impl<T: AddAssign<U>, U> AddAssign<Vector<U>> for Point<T> {
+    fn add_assign(&mut self, v: Vector<U>) {
+        self.x += v.x;
+        self.y += v.y;
+    }
+}
+
+impl<T: SubAssign<U>, U> SubAssign<Vector<U>> for Point<T> {
+    fn sub_assign(&mut self, v: Vector<U>) {
+        self.x -= v.x;
+        self.y -= v.y;
+    }
+}
+
+impl<T> Point<T> {
+    pub fn manhattan_distance(self, other: Self) -> T
+    where
+        T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+    {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    pub fn chebyshev_distance(self, other: Self) -> T
+    where
+        T: Copy + Ord + Sub<Output = T>,
+    {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+
+    pub fn checked_add(self, v: Vector<T>) -> Option<Self>
+    where
+        T: CheckedArith,
+    {
+        Some(Self {
+            x: self.x.checked_add(v.x)?,
+            y: self.y.checked_add(v.y)?,
+        })
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Vector<T>>
+    where
+        T: CheckedArith,
+    {
+        Some(Vector {
+            x: self.x.checked_sub(other.x)?,
+            y: self.y.checked_sub(other.y)?,
+        })
+    }
+
+    // Grid indices are `(row, col)`, i.e. `(y, x)`; `None` for points with a
+    // negative or otherwise unrepresentable coordinate.
+    pub fn to_index(self) -> Option<(usize, usize)>
+    where
+        usize: TryFrom<T>,
+    {
+        Some((usize::try_from(self.y).ok()?, usize::try_from(self.x).ok()?))
+    }
+
+    pub fn from_index((row, col): (usize, usize)) -> Option<Self>
+    where
+        T: TryFrom<usize>,
+    {
+        Some(Self {
+            x: T::try_from(col).ok()?,
+            y: T::try_from(row).ok()?,
+        })
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(p: Point<T>) -> Self {
+        (p.x, p.y)
+    }
+}
+
+// Written the way points appear in the puzzle inputs, so it round-trips
+// through `FromStr`.
+impl<T: fmt::Display> fmt::Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Hash)]
 pub struct Vector<T> {
     pub x: T,
@@ -69,6 +169,45 @@ where
     }
 }
 
+impl<T, U> Sub<Vector<U>> for Vector<T>
+where
+    T: Sub<U>,
+{
+    type Output = Vector<<T as Sub<U>>::Output>;
+
+    fn sub(self, other: Vector<U>) -> Self::Output {
+        Self::Output {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+
+impl<T: Neg> Neg for Vector<T> {
+    type Output = Vector<<T as Neg>::Output>;
+
+    fn neg(self) -> Self::Output {
+        Self::Output {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<T: AddAssign<U>, U> AddAssign<Vector<U>> for Vector<T> {
+    fn add_assign(&mut self, other: Vector<U>) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: SubAssign<U>, U> SubAssign<Vector<U>> for Vector<T> {
+    fn sub_assign(&mut self, other: Vector<U>) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
 impl<T, U> Mul<U> for Vector<T>
 where
     T: Mul<U>,
@@ -100,14 +239,12 @@ where
 }
 
 impl<T> Vector<T> {
+    // Same as `rot90`.
     pub fn ortho(&self) -> Self
     where
-        T: Copy + std::ops::Neg<Output = T>,
+        T: Copy + Neg<Output = T>,
     {
-        Self {
-            x: -self.y,
-            y: self.x,
-        }
+        self.rot90()
     }
 
     pub fn inner_product<U: Mul<T>>(
@@ -119,6 +256,100 @@ impl<T> Vector<T> {
     {
         other.x * self.x + other.y * self.y
     }
+
+    // The z component of the 3D cross product: positive when `other` is
+    // counter-clockwise from `self`, zero when they are parallel.
+    pub fn cross<U: Mul<T>>(self, other: Vector<U>) -> <<U as Mul<T>>::Output as Sub>::Output
+    where
+        T: Copy,
+        <U as Mul<T>>::Output: Sub,
+    {
+        other.y * self.x - other.x * self.y
+    }
+
+    // Rotations are counter-clockwise for a y-up axis; with the puzzles' y-down
+    // grids they appear clockwise.
+    pub fn rot90(self) -> Self
+    where
+        T: Neg<Output = T>,
+    {
+        Self {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+
+    pub fn rot270(self) -> Self
+    where
+        T: Neg<Output = T>,
+    {
+        Self {
+            x: self.y,
+            y: -self.x,
+        }
+    }
+
+    pub fn manhattan_norm(self) -> T
+    where
+        T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>,
+    {
+        abs_diff(self.x, T::default()) + abs_diff(self.y, T::default())
+    }
+
+    pub fn chebyshev_norm(self) -> T
+    where
+        T: Copy + Ord + Default + Sub<Output = T>,
+    {
+        abs_diff(self.x, T::default()).max(abs_diff(self.y, T::default()))
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self>
+    where
+        T: CheckedArith,
+    {
+        Some(Self {
+            x: self.x.checked_add(other.x)?,
+            y: self.y.checked_add(other.y)?,
+        })
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self>
+    where
+        T: CheckedArith,
+    {
+        Some(Self {
+            x: self.x.checked_sub(other.x)?,
+            y: self.y.checked_sub(other.y)?,
+        })
+    }
+
+    pub fn checked_mul(self, scalar: T) -> Option<Self>
+    where
+        T: CheckedArith,
+    {
+        Some(Self {
+            x: self.x.checked_mul(scalar)?,
+            y: self.y.checked_mul(scalar)?,
+        })
+    }
+}
+
+impl<T> From<(T, T)> for Vector<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Vector<T>> for (T, T) {
+    fn from(v: Vector<T>) -> Self {
+        (v.x, v.y)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Vector<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<{},{}>", self.x, self.y)
+    }
 }
 
 impl<T, U> Add<Vector<U>> for Point<T>
@@ -141,44 +372,19 @@ pub struct LineSegment<T> {
     pub p1: Point<T>,
 }
 
-pub enum ParseLineSegmentError<T: FromStr> {
-    InnerType(ParsePointError<T>),
-    LineSegmentFormat(&'static str),
-}
-
 impl<T: FromStr> FromStr for LineSegment<T> {
-    type Err = ParseLineSegmentError<T>;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut part_iter = s.trim().split("->").map(|substr| {
-            Point::<T>::from_str(substr.trim()).map_err(ParseLineSegmentError::InnerType)
-        });
-
-        let p0 = part_iter
-            .next()
-            .ok_or(ParseLineSegmentError::LineSegmentFormat(
-                "no items in string",
-            ))??;
-        let p1 = part_iter
-            .next()
-            .ok_or(ParseLineSegmentError::LineSegmentFormat(
-                "only 1 item in string; requires 2",
-            ))??;
-
-        match part_iter.next() {
-            None => Ok(Self { p0, p1 }),
-            _ => Err(ParseLineSegmentError::LineSegmentFormat(
-                "more than 2 items in string",
-            )),
-        }
+        parse::finish(s, preceded(multispace0, parse::segment))
     }
 }
 
 impl<T> LineSegment<T> {
     pub fn new(p0: (T, T), p1: (T, T)) -> Self {
         Self {
-            p0: Point { x: p0.0, y: p0.1 },
-            p1: Point { x: p1.0, y: p1.1 },
+            p0: p0.into(),
+            p1: p1.into(),
         }
     }
 
@@ -186,14 +392,90 @@ impl<T> LineSegment<T> {
     where
         T: std::cmp::PartialEq + std::cmp::Eq,
     {
-        self.p0.x == self.p1.x
+        self.p0.y == self.p1.y
     }
 
     pub fn is_vert(&self) -> bool
     where
         T: std::cmp::PartialEq + std::cmp::Eq,
     {
-        self.p0.y == self.p1.y
+        self.p0.x == self.p1.x
+    }
+
+    pub fn kind(&self) -> SegmentKind
+    where
+        T: Copy + Ord + Sub<Output = T>,
+    {
+        match (self.is_horiz(), self.is_vert()) {
+            (true, true) => SegmentKind::Degenerate,
+            (true, false) => SegmentKind::Horizontal,
+            (false, true) => SegmentKind::Vertical,
+            _ if abs_diff(self.p0.x, self.p1.x) == abs_diff(self.p0.y, self.p1.y) => {
+                SegmentKind::Diagonal45
+            }
+            _ => SegmentKind::General,
+        }
+    }
+
+    // In grid steps, i.e. the Chebyshev distance between the endpoints: the
+    // number of moves along a horizontal, vertical or 45° segment.
+    pub fn length(&self) -> T
+    where
+        T: Copy + Ord + Sub<Output = T>,
+    {
+        self.p0.chebyshev_distance(self.p1)
+    }
+
+    // The minimum and maximum corners.
+    pub fn bbox(&self) -> (Point<T>, Point<T>)
+    where
+        T: Copy + Ord,
+    {
+        (
+            Point {
+                x: self.p0.x.min(self.p1.x),
+                y: self.p0.y.min(self.p1.y),
+            },
+            Point {
+                x: self.p0.x.max(self.p1.x),
+                y: self.p0.y.max(self.p1.y),
+            },
+        )
+    }
+
+    pub fn contains(&self, p: Point<T>) -> bool
+    where
+        T: Copy + TryInto<i128>,
+    {
+        let delta = self.orientation_wide();
+        match steps_along(widen(p) - widen(self.p0), delta) {
+            Some(k) => 0 <= k && k <= gcd(delta.x, delta.y),
+            None => false,
+        }
+    }
+
+    // Every point with integer coordinates on the segment, from `p0` to `p1`.
+    pub fn lattice_points(&self) -> impl Iterator<Item = Point<T>>
+    where
+        T: Copy + TryInto<i128> + TryFrom<i128>,
+    {
+        let p0 = widen(self.p0);
+        let delta = self.orientation_wide();
+        let steps = gcd(delta.x, delta.y);
+        let step = if steps == 0 {
+            Vector::default()
+        } else {
+            delta / steps
+        };
+
+        (0..=steps).map(move |i| narrow(p0 + step * i))
+    }
+
+    fn orientation_wide(&self) -> Vector<i128>
+    where
+        T: Copy + TryInto<i128>,
+    {
+        widen(self.p1) - widen(self.p0)
     }
 
     pub fn orientation(&self) -> Vector<<T as Sub>::Output>
@@ -203,17 +485,423 @@ impl<T> LineSegment<T> {
         self.p1 - self.p0
     }
 
-    /*
-    pub fn intersects(&self, &other: Self) -> bool {
-        let self_v0 = self.p0 - Default::default();
-        let self_v1 = self.p1 - Default::default();
-        let other_v0 = other.v0 - Default::default();
-        let other_v1 = other.v1 - Default::default();
+    pub fn intersects(&self, other: &Self) -> Result<bool, IntersectionOverflow>
+    where
+        T: Copy + TryInto<i128> + TryFrom<i128>,
+    {
+        Ok(!matches!(self.intersection(other)?, Intersection::Disjoint))
+    }
+
+    // Exact intersection of two closed segments. Parallel and collinear cases
+    // never overflow; locating a crossing can need more than `i128`, which only
+    // happens with coordinates wider than 32 bits.
+    pub fn intersection(&self, other: &Self) -> Result<Intersection<T>, IntersectionOverflow>
+    where
+        T: Copy + TryInto<i128> + TryFrom<i128>,
+    {
+        let (p0, p1, q0, q1) = (
+            widen(self.p0),
+            widen(self.p1),
+            widen(other.p0),
+            widen(other.p1),
+        );
+        let (d1, d2, r) = (p1 - p0, q1 - q0, q0 - p0);
+        let zero = Vector::default();
+        let (s1, s2) = (primitive(d1), primitive(d2));
+
+        if d1 != zero && d2 != zero && s1 != s2 && s1 != -s2 {
+            let cross = |a: Vector<i128>, b: Vector<i128>| {
+                let (ab, ba) = (a.x.checked_mul(b.y), a.y.checked_mul(b.x));
+                ab.zip(ba)
+                    .and_then(|(ab, ba)| ab.checked_sub(ba))
+                    .ok_or(IntersectionOverflow)
+            };
+            let denom = cross(d1, d2)?;
+            let (sign, denom) = (denom.signum(), denom.abs());
+            let t = sign * cross(r, d2)?;
+            let u = sign * cross(r, d1)?;
+            if !(0..=denom).contains(&t) || !(0..=denom).contains(&u) {
+                return Ok(Intersection::Disjoint);
+            }
+
+            let offset = Vector {
+                x: d1.x.checked_mul(t).ok_or(IntersectionOverflow)?,
+                y: d1.y.checked_mul(t).ok_or(IntersectionOverflow)?,
+            };
+            if offset.x % denom != 0 || offset.y % denom != 0 {
+                return Ok(Intersection::OffLattice);
+            }
+            return Ok(Intersection::Point(narrow(p0 + offset / denom)));
+        }
+
+        // Parallel (or degenerate): only collinear segments can meet.
+        let d = if d1 != zero { d1 } else { d2 };
+        if d == zero {
+            return Ok(if r == zero {
+                Intersection::Point(self.p0)
+            } else {
+                Intersection::Disjoint
+            });
+        }
+        // Positions along the shared line, in lattice steps from `p0`.
+        let along = |p: Point<i128>| steps_along(p - p0, d);
+        let (a0, a1) = (0, along(p1).expect("on its own line"));
+        let (b0, b1) = match (along(q0), along(q1)) {
+            (Some(b0), Some(b1)) => (b0, b1),
+            _ => return Ok(Intersection::Disjoint),
+        };
+        let lo = a0.min(a1).max(b0.min(b1));
+        let hi = a0.max(a1).min(b0.max(b1));
+        if lo > hi {
+            return Ok(Intersection::Disjoint);
+        }
+
+        let endpoint = |s: i128| {
+            IntoIterator::into_iter([p0, p1, q0, q1])
+                .find(|&p| along(p) == Some(s))
+                .map(narrow)
+                .expect("overlap bounds are endpoints")
+        };
+        Ok(if lo == hi {
+            Intersection::Point(endpoint(lo))
+        } else if a0 <= a1 {
+            Intersection::Segment(LineSegment {
+                p0: endpoint(lo),
+                p1: endpoint(hi),
+            })
+        } else {
+            Intersection::Segment(LineSegment {
+                p0: endpoint(hi),
+                p1: endpoint(lo),
+            })
+        })
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SegmentKind {
+    Degenerate,
+    Horizontal,
+    Vertical,
+    Diagonal45,
+    General,
+}
+
+// Every primitive integer coordinate fits, except `u128`s above `i128::MAX`,
+// which panic.
+fn widen<T: TryInto<i128>>(p: Point<T>) -> Point<i128> {
+    Point {
+        x: p.x.try_into().ok().expect("coordinates fit in i128"),
+        y: p.y.try_into().ok().expect("coordinates fit in i128"),
+    }
+}
+
+// The smallest lattice step in the direction of `d`.
+fn primitive(d: Vector<i128>) -> Vector<i128> {
+    match gcd(d.x, d.y) {
+        0 => d,
+        g => d / g,
+    }
+}
+
+// The `k` with `r == k * primitive(d)`, if there is one; `d` must be non-zero.
+// Never overflows, unlike testing `r.cross(d) == 0`.
+fn steps_along(r: Vector<i128>, d: Vector<i128>) -> Option<i128> {
+    let s = primitive(d);
+    let (num, den) = if s.x != 0 { (r.x, s.x) } else { (r.y, s.y) };
+    if den == 0 || num % den != 0 {
+        return if r == Vector::default() {
+            Some(0)
+        } else {
+            None
+        };
+    }
+    let k = num / den;
+    (s.x.checked_mul(k)? == r.x && s.y.checked_mul(k)? == r.y).then_some(k)
+}
+
+// Only used for points lying between two endpoints, which fit in `T`.
+fn narrow<T: TryFrom<i128>>(p: Point<i128>) -> Point<T> {
+    Point {
+        x: T::try_from(p.x).ok().expect("lies within the input bounds"),
+        y: T::try_from(p.y).ok().expect("lies within the input bounds"),
+    }
+}
+
+// The overlap of two segments. Crossings at a non-integer point (e.g. between
+// two diagonals of opposite parity) are reported without their position.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Intersection<T> {
+    Disjoint,
+    Point(Point<T>),
+    Segment(LineSegment<T>),
+    OffLattice,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct IntersectionOverflow;
+
+impl fmt::Display for IntersectionOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "locating the intersection overflows i128")
+    }
+}
+
+impl std::error::Error for IntersectionOverflow {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vector_ops() {
+        let u = Vector { x: 3, y: -4 };
+        let v = Vector::from((1, 2));
+        assert_eq!(u - v, Vector { x: 2, y: -6 });
+        assert_eq!(-u, Vector { x: -3, y: 4 });
+        assert_eq!(u.cross(v), 10);
+        assert_eq!(v.cross(u), -10);
+        assert_eq!(u.cross(u * 2), 0);
+        assert_eq!(v.rot90(), Vector { x: -2, y: 1 });
+        assert_eq!(v.ortho(), v.rot90());
+        assert_eq!(v.rot90().rot270(), v);
+        assert_eq!(v.rot90().rot90(), -v);
+        assert_eq!((u.manhattan_norm(), u.chebyshev_norm()), (7, 4));
+
+        let mut w = u;
+        w += v;
+        w -= Vector { x: 0, y: 1 };
+        assert_eq!(<(i32, i32)>::from(w), (4, -3));
+        assert_eq!(w.to_string(), "<4,-3>");
+    }
 
-        let self_orth = self.orientation().ortho();
-        let other_orth = other.orientation().ortho();
+    #[test]
+    fn test_point_ops() {
+        let p = Point::from((5, 1));
+        let q = Point { x: 2, y: 7 };
+        assert_eq!((p.manhattan_distance(q), p.chebyshev_distance(q)), (9, 6));
+        assert_eq!(q - (q - p), p);
+
+        let mut r = p;
+        r += Vector { x: 1, y: 1 };
+        r -= Vector { x: 0, y: 2 };
+        assert_eq!(r, Point { x: 6, y: 0 });
+
+        assert_eq!(p.to_string(), "5,1");
+        assert_eq!(p.to_string().parse(), Ok(p));
+        assert_eq!(" 5 , +1 \n".parse(), Ok(p));
+
+        let err = " 5;1".parse::<Point<i32>>().unwrap_err();
+        assert_eq!((err.line, err.column), (1, 3));
+        assert_eq!("\t0,9->5,+9".parse(), Ok(LineSegment::new((0, 9), (5, 9))));
+    }
+
+    fn intersect(a: ((i32, i32), (i32, i32)), b: ((i32, i32), (i32, i32))) -> Intersection<i32> {
+        LineSegment::new(a.0, a.1)
+            .intersection(&LineSegment::new(b.0, b.1))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_intersection() {
+        // proper crossing, touching at an endpoint, and passing by
+        assert_eq!(
+            intersect(((0, 0), (4, 4)), ((0, 4), (4, 0))),
+            Intersection::Point(Point { x: 2, y: 2 })
+        );
+        assert_eq!(
+            intersect(((0, 0), (4, 0)), ((4, 0), (4, 3))),
+            Intersection::Point(Point { x: 4, y: 0 })
+        );
+        assert_eq!(
+            intersect(((0, 0), (4, 0)), ((2, 1), (2, 3))),
+            Intersection::Disjoint
+        );
+        assert_eq!(
+            intersect(((0, 0), (1, 1)), ((0, 1), (1, 0))),
+            Intersection::OffLattice
+        );
+
+        // collinear overlap keeps the direction of `self`
+        assert_eq!(
+            intersect(((5, 5), (0, 0)), ((2, 2), (8, 8))),
+            Intersection::Segment(LineSegment::new((5, 5), (2, 2)))
+        );
+        assert_eq!(
+            intersect(((0, 0), (3, 0)), ((3, 0), (6, 0))),
+            Intersection::Point(Point { x: 3, y: 0 })
+        );
+        assert_eq!(
+            intersect(((0, 0), (2, 0)), ((3, 0), (6, 0))),
+            Intersection::Disjoint
+        );
+        assert_eq!(
+            intersect(((0, 0), (2, 0)), ((0, 1), (2, 1))),
+            Intersection::Disjoint
+        );
+
+        // degenerate segments
+        assert_eq!(
+            intersect(((1, 1), (1, 1)), ((0, 0), (3, 3))),
+            Intersection::Point(Point { x: 1, y: 1 })
+        );
+        assert_eq!(
+            intersect(((1, 1), (1, 1)), ((1, 2), (1, 2))),
+            Intersection::Disjoint
+        );
+        assert_eq!(
+            intersect(((1, 2), (1, 2)), ((0, 0), (3, 3))),
+            Intersection::Disjoint
+        );
+
+        // cross products well outside i32
+        let big = 2_000_000_000;
+        assert_eq!(
+            intersect(((-big, -big), (big, big)), ((-big, big), (big, -big))),
+            Intersection::Point(Point { x: 0, y: 0 })
+        );
+        assert_eq!(
+            LineSegment::new((0, 0), (1, 0)).intersects(&LineSegment::new((0, 1), (1, 1))),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn test_intersection_64_bit() {
+        let m = i64::MAX;
+        let rising = LineSegment::new((-m, -m), (m, m));
+        let falling = LineSegment::new((-m, m), (m, -m));
+
+        // the crossing needs ~130-bit products, which are reported, not wrapped
+        assert_eq!(rising.intersection(&falling), Err(IntersectionOverflow));
+        assert_eq!(rising.intersects(&falling), Err(IntersectionOverflow));
+
+        // collinear and parallel cases stay exact at any scale
+        assert_eq!(
+            rising.intersection(&LineSegment::new((0, 0), (m, m))),
+            Ok(Intersection::Segment(LineSegment::new((0, 0), (m, m))))
+        );
+        assert_eq!(
+            rising.intersection(&LineSegment::new((m - 1, m), (-m, 1 - m))),
+            Ok(Intersection::Disjoint)
+        );
+        assert_eq!(
+            rising.intersection(&LineSegment::new((m, m), (m, m))),
+            Ok(Intersection::Point(Point { x: m, y: m }))
+        );
+        assert!(rising.contains(Point { x: m - 7, y: m - 7 }));
+        assert!(!rising.contains(Point { x: m - 7, y: m - 8 }));
+        assert!(!LineSegment::new((0, 0), (m, m - 1)).contains(Point { x: 1, y: 1 }));
+
+        // crossings that fit are still found with 64-bit coordinates
+        let big = 1i64 << 40;
+        assert_eq!(
+            LineSegment::new((-big, 0), (big, 0))
+                .intersection(&LineSegment::new((3, -big), (3, big))),
+            Ok(Intersection::Point(Point { x: 3, y: 0 }))
+        );
+    }
+
+    #[test]
+    fn test_segment_usize() {
+        let diagonal = LineSegment::new((0usize, 0), (4, 4));
+        assert!(diagonal.contains(Point { x: 2, y: 2 }));
+        assert_eq!(diagonal.lattice_points().count(), 5);
+        assert_eq!(
+            diagonal.intersection(&LineSegment::new((0, 4), (4, 0))),
+            Ok(Intersection::Point(Point { x: 2, y: 2 }))
+        );
+
+        let far = u32::MAX as usize;
+        assert_eq!(
+            LineSegment::new((far, 0), (far, far)).intersects(&diagonal),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn test_segment_kind() {
+        let horiz = LineSegment::new((0, 9), (5, 9));
+        let vert = LineSegment::new((7, 0), (7, 4));
+        assert!(horiz.is_horiz() && !horiz.is_vert());
+        assert!(vert.is_vert() && !vert.is_horiz());
+
+        assert_eq!(horiz.kind(), SegmentKind::Horizontal);
+        assert_eq!(vert.kind(), SegmentKind::Vertical);
+        assert_eq!(
+            LineSegment::new((8, 0), (0, 8)).kind(),
+            SegmentKind::Diagonal45
+        );
+        assert_eq!(
+            LineSegment::new((6u8, 4), (2, 0)).kind(),
+            SegmentKind::Diagonal45
+        );
+        assert_eq!(
+            LineSegment::new((0, 0), (4, 2)).kind(),
+            SegmentKind::General
+        );
+        assert_eq!(
+            LineSegment::new((3, 3), (3, 3)).kind(),
+            SegmentKind::Degenerate
+        );
+    }
+
+    #[test]
+    fn test_segment_measures() {
+        let seg = LineSegment::new((6, -1), (0, 2));
+        assert_eq!(seg.length(), 6);
+        assert_eq!(seg.bbox(), (Point { x: 0, y: -1 }, Point { x: 6, y: 2 }));
+        assert!(seg.contains(Point { x: 4, y: 0 }));
+        assert!(seg.contains(Point { x: 0, y: 2 }));
+        assert!(!seg.contains(Point { x: 5, y: 0 }));
+        assert!(!seg.contains(Point { x: -2, y: 3 }));
+
+        assert_eq!(
+            seg.lattice_points().collect::<Vec<_>>(),
+            vec![
+                Point { x: 6, y: -1 },
+                Point { x: 4, y: 0 },
+                Point { x: 2, y: 1 },
+                Point { x: 0, y: 2 }
+            ]
+        );
+        assert_eq!(LineSegment::new((1, 0), (3, 1)).lattice_points().count(), 2);
+        assert_eq!(
+            LineSegment::new((2u32, 2), (2, 2))
+                .lattice_points()
+                .collect::<Vec<_>>(),
+            vec![Point { x: 2, y: 2 }]
+        );
+        assert_eq!(
+            LineSegment::new((9u8, 4), (3, 4)).lattice_points().count(),
+            7
+        );
+    }
 
-        assert_eq!(self_ortho)()
+    #[test]
+    fn test_checked_and_index() {
+        let p = Point {
+            x: i32::MAX - 1,
+            y: 0,
+        };
+        assert_eq!(
+            p.checked_add(Vector { x: 1, y: 0 }),
+            Some(Point { x: i32::MAX, y: 0 })
+        );
+        assert_eq!(p.checked_add(Vector { x: 2, y: 0 }), None);
+        assert_eq!(
+            Point { x: 0u8, y: 3 }.checked_sub(Point { x: 1, y: 0 }),
+            None
+        );
+        assert_eq!(Vector { x: 1 << 20, y: 1 }.checked_mul(1 << 11), None);
+        assert_eq!(
+            Vector { x: 1, y: -1 }.checked_sub(Vector { x: 2, y: 2 }),
+            Some(Vector { x: -1, y: -3 })
+        );
+
+        assert_eq!(Point { x: 3, y: 1 }.to_index(), Some((1, 3)));
+        assert_eq!(Point { x: -1, y: 1 }.to_index(), None);
+        assert_eq!(Point::<i32>::from_index((1, 3)), Some(Point { x: 3, y: 1 }));
+        assert_eq!(Point::<i8>::from_index((0, 200)), None);
     }
-    */
 }
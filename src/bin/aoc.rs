@@ -0,0 +1,255 @@
+use aoc_lib::days::day15::{self, Day15, Highlight};
+use aoc_lib::days::{self, Solution};
+use aoc_lib::solver::Solver;
+use aoc_lib::utils::AnyError;
+
+use std::io::Read;
+use std::path::PathBuf;
+use std::time::Instant;
+
+const USAGE: &str = "usage:
+    aoc run --day <DAY> [--part <PART>] [--input <PATH>]
+    aoc run --all [--inputs <DIR>]
+    aoc path [--input <PATH>] [--tiles <ROWS>x<COLS>] [--marker <CHAR>]
+
+Inputs default to `<DIR>/day<DAY>.txt`, with <DIR> defaulting to `inputs`.
+Pass `--input -` to read the puzzle input from stdin.
+
+`path` draws the lowest-risk day 15 path, in color unless a marker is given;
+`--tiles 5x5` is the part 2 map.";
+
+#[derive(Debug)]
+enum Command {
+    Run(RunArgs),
+    Path(PathArgs),
+}
+
+#[derive(Debug)]
+enum Selection {
+    All,
+    Day(u32, Option<u32>),
+}
+
+#[derive(Debug)]
+struct RunArgs {
+    selection: Selection,
+    input: Option<PathBuf>,
+    inputs_dir: PathBuf,
+}
+
+#[derive(Debug)]
+struct PathArgs {
+    input: PathBuf,
+    tiles: (usize, usize),
+    highlight: Highlight,
+}
+
+fn parse_number(flag: &str, value: Option<String>) -> Result<u32, String> {
+    let value = value.ok_or(format!("missing value for `{}`", flag))?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid value for `{}`: {:?}", flag, value))
+}
+
+fn parse_tiles(value: Option<String>) -> Result<(usize, usize), String> {
+    let value = value.ok_or("missing value for `--tiles`")?;
+    let invalid = || format!("invalid value for `--tiles`: {:?}", value);
+    let (rows, cols) = value.split_once('x').ok_or_else(invalid)?;
+    match (rows.parse(), cols.parse()) {
+        (Ok(rows), Ok(cols)) if rows > 0 && cols > 0 => Ok((rows, cols)),
+        _ => Err(invalid()),
+    }
+}
+
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    match args.next().as_deref() {
+        Some("run") => parse_run_args(args).map(Command::Run),
+        Some("path") => parse_path_args(args).map(Command::Path),
+        Some(cmd) => Err(format!("unknown command {:?}", cmd)),
+        None => Err("no command given".to_string()),
+    }
+}
+
+fn parse_run_args<I: Iterator<Item = String>>(mut args: I) -> Result<RunArgs, String> {
+    let mut all = false;
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut inputs_dir = PathBuf::from("inputs");
+
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--all" => all = true,
+            "--day" => day = Some(parse_number("--day", args.next())?),
+            "--part" => part = Some(parse_number("--part", args.next())?),
+            "--input" => input = Some(args.next().ok_or("missing value for `--input`")?.into()),
+            "--inputs" => inputs_dir = args.next().ok_or("missing value for `--inputs`")?.into(),
+            _ => return Err(format!("unknown argument {:?}", flag)),
+        }
+    }
+
+    let selection = match (all, day) {
+        (true, None) if part.is_none() && input.is_none() => Selection::All,
+        (true, _) => {
+            return Err(
+                "`--all` cannot be combined with `--day`, `--part` or `--input`".to_string(),
+            )
+        }
+        (false, Some(day)) => Selection::Day(day, part),
+        (false, None) => return Err("one of `--day` or `--all` is required".to_string()),
+    };
+
+    Ok(RunArgs {
+        selection,
+        input,
+        inputs_dir,
+    })
+}
+
+fn parse_path_args<I: Iterator<Item = String>>(mut args: I) -> Result<PathArgs, String> {
+    let mut path_args = PathArgs {
+        input: PathBuf::from("inputs/day15.txt"),
+        tiles: (1, 1),
+        highlight: Highlight::Ansi,
+    };
+
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--input" => path_args.input = args.next().ok_or("missing value for `--input`")?.into(),
+            "--tiles" => path_args.tiles = parse_tiles(args.next())?,
+            "--marker" => {
+                let value = args.next().ok_or("missing value for `--marker`")?;
+                let mut chars = value.chars();
+                path_args.highlight = match (chars.next(), chars.next()) {
+                    (Some(c), None) => Highlight::Marker(c),
+                    _ => return Err(format!("invalid value for `--marker`: {:?}", value)),
+                };
+            }
+            _ => return Err(format!("unknown argument {:?}", flag)),
+        }
+    }
+
+    Ok(path_args)
+}
+
+fn read_input(path: &PathBuf) -> Result<String, AnyError> {
+    let mut buffer = String::new();
+    if path.as_os_str() == "-" {
+        std::io::stdin().read_to_string(&mut buffer)?;
+    } else {
+        buffer = std::fs::read_to_string(path)
+            .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+    }
+
+    Ok(buffer)
+}
+
+fn run_solution(solution: &Solution, input: &str) -> bool {
+    let start = Instant::now();
+    let result = (solution.solve)(input);
+    let elapsed = start.elapsed();
+
+    match result {
+        Ok(answer) if answer.contains('\n') => {
+            println!(
+                "day {} part {} ({:.2?}):\n{}",
+                solution.day, solution.part, elapsed, answer
+            );
+            true
+        }
+        Ok(answer) => {
+            println!(
+                "day {} part {}: {} ({:.2?})",
+                solution.day, solution.part, answer, elapsed
+            );
+            true
+        }
+        Err(err) => {
+            eprintln!(
+                "day {} part {}: error: {:#}",
+                solution.day, solution.part, err
+            );
+            false
+        }
+    }
+}
+
+fn run(args: RunArgs) -> Result<bool, AnyError> {
+    let days: Vec<u32> = match args.selection {
+        Selection::All => {
+            let mut days: Vec<_> = days::REGISTRY.iter().map(|s| s.day).collect();
+            days.dedup();
+            days
+        }
+        Selection::Day(day, _) => vec![day],
+    };
+
+    let mut all_ok = true;
+    for day in days {
+        let solutions: Vec<&Solution> = match args.selection {
+            Selection::Day(_, Some(part)) => days::find(day, part).into_iter().collect(),
+            _ => days::for_day(day).collect(),
+        };
+        if solutions.is_empty() {
+            return Err(match args.selection {
+                Selection::Day(_, Some(part)) => {
+                    format!("no solution registered for day {} part {}", day, part)
+                }
+                _ => format!("no solution registered for day {}", day),
+            }
+            .into());
+        }
+
+        let path = args
+            .input
+            .clone()
+            .unwrap_or_else(|| args.inputs_dir.join(format!("day{}.txt", day)));
+        let input = match read_input(&path) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("day {}: {}", day, err);
+                all_ok = false;
+                continue;
+            }
+        };
+
+        for solution in solutions {
+            all_ok &= run_solution(solution, &input);
+        }
+    }
+
+    Ok(all_ok)
+}
+
+fn run_path(args: PathArgs) -> Result<(), AnyError> {
+    let input = read_input(&args.input)?;
+    let cave_map = Day15::parse(&input)?;
+    println!(
+        "{}",
+        day15::path_report(&cave_map, args.tiles, args.highlight)
+    );
+    Ok(())
+}
+
+fn main() {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(msg) => {
+            eprintln!("error: {}\n\n{}", msg, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let result = match args {
+        Command::Run(args) => run(args),
+        Command::Path(args) => run_path(args).map(|()| true),
+    };
+    match result {
+        Ok(true) => (),
+        Ok(false) => std::process::exit(1),
+        Err(err) => {
+            eprintln!("error: {}", err);
+            std::process::exit(1);
+        }
+    }
+}
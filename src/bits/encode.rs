@@ -0,0 +1,263 @@
+use super::{BitsError, HexBits, LengthType, PacketBody, PacketTree};
+use num_bigint::BigUint;
+
+#[derive(Debug, Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    bit_len: usize,
+}
+
+impl BitWriter {
+    fn set_bit(&mut self, index: usize, bit: bool) {
+        let mask = 1 << (7 - index % 8);
+        if bit {
+            self.bytes[index / 8] |= mask;
+        } else {
+            self.bytes[index / 8] &= !mask;
+        }
+    }
+
+    fn push(&mut self, value: u64, bitlen: usize) {
+        for i in (0..bitlen).rev() {
+            if self.bit_len.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            self.set_bit(self.bit_len, (value >> i) & 1 == 1);
+            self.bit_len += 1;
+        }
+    }
+
+    fn overwrite(&mut self, start: usize, value: u64, bitlen: usize) {
+        for i in 0..bitlen {
+            self.set_bit(start + i, (value >> (bitlen - 1 - i)) & 1 == 1);
+        }
+    }
+}
+
+fn write_literal(writer: &mut BitWriter, value: &BigUint) {
+    let nibbles = value.to_radix_be(16);
+    for (i, &nibble) in nibbles.iter().enumerate() {
+        let bit_continue = (i + 1 != nibbles.len()) as u64;
+        writer.push(bit_continue, 1);
+        writer.push(nibble as u64, 4);
+    }
+}
+
+fn write_packet(writer: &mut BitWriter, tree: &PacketTree) -> Result<(), BitsError> {
+    if tree.version > 7 {
+        return Err(BitsError::VersionOverflow {
+            version: tree.version,
+            offset: writer.bit_len,
+        });
+    }
+    writer.push(tree.version as u64, 3);
+
+    match &tree.body {
+        PacketBody::Literal(value) => {
+            writer.push(4, 3);
+            write_literal(writer, value);
+        }
+        PacketBody::Operator {
+            op,
+            length_type,
+            children,
+        } => {
+            writer.push(op.type_id() as u64, 3);
+
+            // The length field is written as zeros and patched once the
+            // sub-packets have been encoded.
+            let (len_type_id, field_len) = match length_type {
+                LengthType::TotalLength(_) => (0, 15),
+                LengthType::SubPackets(_) => (1, 11),
+            };
+            writer.push(len_type_id, 1);
+            let field_start = writer.bit_len;
+            writer.push(0, field_len);
+
+            let children_start = writer.bit_len;
+            for child in children {
+                write_packet(writer, child)?;
+            }
+
+            let len = match length_type {
+                LengthType::TotalLength(_) => writer.bit_len - children_start,
+                LengthType::SubPackets(_) => children.len(),
+            };
+            if len >= 1 << field_len {
+                return Err(BitsError::LengthOverflow {
+                    offset: field_start,
+                });
+            }
+            writer.overwrite(field_start, len as u64, field_len);
+        }
+    }
+
+    Ok(())
+}
+
+impl PacketTree {
+    pub fn encode(&self) -> Result<HexBits, BitsError> {
+        let mut writer = BitWriter::default();
+        write_packet(&mut writer, self)?;
+        Ok(HexBits(writer.bytes))
+    }
+
+    pub fn to_hex(&self) -> Result<String, BitsError> {
+        self.encode().map(|hbits| hbits.to_string())
+    }
+
+    pub fn with_lengths_recomputed(&self) -> Result<Self, BitsError> {
+        PacketTree::decode(&self.encode()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bits::OperatorType;
+    use core::str::FromStr;
+    use rstest::*;
+
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: u64) -> u64 {
+            self.next() % n
+        }
+    }
+
+    fn random_tree(rng: &mut XorShift, depth: usize) -> PacketTree {
+        let version = rng.below(8) as u8;
+        if depth == 0 || rng.below(3) == 0 {
+            let value = rng.next() >> rng.below(64);
+            return PacketTree::literal(version, value);
+        }
+
+        let op = [
+            OperatorType::Sum,
+            OperatorType::Product,
+            OperatorType::Minimum,
+            OperatorType::Maximum,
+            OperatorType::GreaterThan,
+            OperatorType::LessThan,
+            OperatorType::EqualTo,
+        ][rng.below(7) as usize];
+        let child_count = match op {
+            OperatorType::GreaterThan | OperatorType::LessThan | OperatorType::EqualTo => 2,
+            _ => 1 + rng.below(4) as usize,
+        };
+        let children = (0..child_count)
+            .map(|_| random_tree(rng, depth - 1))
+            .collect::<Vec<_>>();
+
+        let length_type = match rng.below(2) {
+            0 => LengthType::TotalLength(0),
+            _ => LengthType::SubPackets(0),
+        };
+        PacketTree::operator(version, op, length_type, children)
+            .with_lengths_recomputed()
+            .unwrap()
+    }
+
+    #[rstest(
+        input,
+        case("D2FE28"),
+        case("38006F45291200"),
+        case("EE00D40C823060"),
+        case("8A004A801A8002F478"),
+        case("620080001611562C8802118E34"),
+        case("C0015000016115A2E0802F182340"),
+        case("A0016C880162017C3686B18A3D4780"),
+        case("9C0141080250320F1802104A08")
+    )]
+    fn test_encode_egs(input: &'static str) {
+        let tree = PacketTree::decode(&HexBits::from_str(input).unwrap()).unwrap();
+        let hex = tree.to_hex().unwrap();
+
+        assert!(input.starts_with(hex.trim_end_matches('0')));
+        assert_eq!(
+            PacketTree::decode(&HexBits::from_str(&hex).unwrap()),
+            Ok(tree)
+        );
+    }
+
+    #[test]
+    fn test_encode_literal() {
+        assert_eq!(
+            PacketTree::literal(6, 2021).to_hex(),
+            Ok("D2FE28".to_string())
+        );
+        assert_eq!(PacketTree::literal(0, 0).to_hex(), Ok("1000".to_string()));
+    }
+
+    #[test]
+    fn test_encode_errors() {
+        assert_eq!(
+            PacketTree::literal(8, 0).encode(),
+            Err(BitsError::VersionOverflow {
+                version: 8,
+                offset: 0
+            })
+        );
+
+        let too_many = PacketTree::operator(
+            0,
+            OperatorType::Sum,
+            LengthType::SubPackets(0),
+            vec![PacketTree::literal(0, 0); 1 << 11],
+        );
+        assert_eq!(
+            too_many.encode(),
+            Err(BitsError::LengthOverflow { offset: 7 })
+        );
+
+        let too_long = PacketTree::operator(
+            0,
+            OperatorType::Sum,
+            LengthType::TotalLength(0),
+            vec![PacketTree::literal(0, 0); 3000],
+        );
+        assert_eq!(
+            too_long.encode(),
+            Err(BitsError::LengthOverflow { offset: 7 })
+        );
+
+        let nested = PacketTree::operator(
+            0,
+            OperatorType::Sum,
+            LengthType::SubPackets(0),
+            vec![PacketTree::literal(0, 1), PacketTree::literal(9, 2)],
+        );
+        assert_eq!(
+            nested.encode(),
+            Err(BitsError::VersionOverflow {
+                version: 9,
+                offset: 29
+            })
+        );
+    }
+
+    #[test]
+    fn test_round_trip_random_trees() {
+        let mut rng = XorShift(0x2021_1216);
+        for _ in 0..500 {
+            let tree = random_tree(&mut rng, 4);
+            let hbits = tree.encode().unwrap();
+            let decoded = PacketTree::decode(&hbits).unwrap();
+
+            assert_eq!(decoded, tree);
+            assert_eq!(decoded.encode(), Ok(hbits));
+            assert_eq!(
+                decoded.evaluate_as::<BigUint>(),
+                tree.evaluate_as::<BigUint>()
+            );
+        }
+    }
+}
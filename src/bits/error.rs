@@ -0,0 +1,148 @@
+use super::OperatorType;
+use crate::nom_utils::{bits_remaining, BitInput, BitOffsetError};
+
+use core::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitsError {
+    InvalidDigit {
+        offset: usize,
+    },
+    UnknownTypeId {
+        type_id: u8,
+        offset: usize,
+    },
+    WrongOperandCount {
+        op: OperatorType,
+        expected: usize,
+        found: usize,
+        offset: usize,
+    },
+    EmptyOperator {
+        op: OperatorType,
+        offset: usize,
+    },
+    Truncated {
+        offset: usize,
+    },
+    LiteralOverflow {
+        offset: usize,
+    },
+    ArithmeticOverflow {
+        op: OperatorType,
+        offset: usize,
+    },
+    LengthOverflow {
+        offset: usize,
+    },
+    VersionOverflow {
+        version: u8,
+        offset: usize,
+    },
+    TrailingGarbage {
+        offset: usize,
+    },
+    Io {
+        kind: std::io::ErrorKind,
+        offset: usize,
+    },
+}
+
+impl BitsError {
+    pub fn offset(&self) -> usize {
+        match *self {
+            Self::InvalidDigit { offset }
+            | Self::UnknownTypeId { offset, .. }
+            | Self::WrongOperandCount { offset, .. }
+            | Self::EmptyOperator { offset, .. }
+            | Self::Truncated { offset }
+            | Self::LiteralOverflow { offset }
+            | Self::ArithmeticOverflow { offset, .. }
+            | Self::LengthOverflow { offset }
+            | Self::VersionOverflow { offset, .. }
+            | Self::TrailingGarbage { offset }
+            | Self::Io { offset, .. } => offset,
+        }
+    }
+
+    fn offset_mut(&mut self) -> &mut usize {
+        match self {
+            Self::InvalidDigit { offset }
+            | Self::UnknownTypeId { offset, .. }
+            | Self::WrongOperandCount { offset, .. }
+            | Self::EmptyOperator { offset, .. }
+            | Self::Truncated { offset }
+            | Self::LiteralOverflow { offset }
+            | Self::ArithmeticOverflow { offset, .. }
+            | Self::LengthOverflow { offset }
+            | Self::VersionOverflow { offset, .. }
+            | Self::TrailingGarbage { offset }
+            | Self::Io { offset, .. } => offset,
+        }
+    }
+
+    // While parsing, offsets are recorded as the number of bits left in the
+    // stream; this converts them to positions from the start of the stream.
+    pub(crate) fn rebased(mut self, total_bits: usize) -> Self {
+        let offset = self.offset_mut();
+        *offset = total_bits - *offset;
+        self
+    }
+}
+
+impl fmt::Display for BitsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidDigit { .. } => write!(f, "invalid hex digit"),
+            Self::UnknownTypeId { type_id, .. } => write!(f, "unknown packet type id {}", type_id),
+            Self::WrongOperandCount {
+                op,
+                expected,
+                found,
+                ..
+            } => write!(
+                f,
+                "{:?} operator expects {} operands, found {}",
+                op, expected, found
+            ),
+            Self::EmptyOperator { op, .. } => write!(f, "{:?} operator has no operands", op),
+            Self::Truncated { .. } => write!(f, "truncated packet"),
+            Self::LiteralOverflow { .. } => write!(f, "literal does not fit in the numeric type"),
+            Self::ArithmeticOverflow { op, .. } => write!(f, "{:?} operator overflowed", op),
+            Self::LengthOverflow { .. } => write!(f, "sub-packet length overflow"),
+            Self::VersionOverflow { version, .. } => {
+                write!(f, "packet version {} does not fit in 3 bits", version)
+            }
+            Self::TrailingGarbage { .. } => write!(f, "trailing data after packet"),
+            Self::Io { kind, .. } => write!(f, "read failed ({:?})", kind),
+        }?;
+        write!(f, " at bit {}", self.offset())
+    }
+}
+
+impl std::error::Error for BitsError {}
+
+impl<I> nom::error::ParseError<BitInput<I>> for BitsError
+where
+    I: nom::InputLength,
+{
+    fn from_error_kind(input: BitInput<I>, kind: nom::error::ErrorKind) -> Self {
+        let offset = bits_remaining(&input);
+        match kind {
+            nom::error::ErrorKind::LengthValue => Self::LengthOverflow { offset },
+            nom::error::ErrorKind::TooLarge => Self::LiteralOverflow { offset },
+            _ => Self::Truncated { offset },
+        }
+    }
+
+    fn append(_input: BitInput<I>, _kind: nom::error::ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+impl BitOffsetError for BitsError {
+    fn shifted(mut self, bits: usize) -> Self {
+        *self.offset_mut() += bits;
+        self
+    }
+}
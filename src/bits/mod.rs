@@ -0,0 +1,438 @@
+mod encode;
+mod error;
+mod number;
+mod parse;
+mod render;
+mod stream;
+
+pub use error::BitsError;
+pub use number::BitsNumber;
+pub use parse::packet;
+pub use stream::{Encoding, Event, StreamDecoder};
+
+use num_bigint::BigUint;
+
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
+use std::convert::TryInto;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct HexBits(pub Vec<u8>);
+
+impl FromStr for HexBits {
+    type Err = BitsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                c.to_digit(16)
+                    .ok_or(BitsError::InvalidDigit { offset: 4 * i })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // An odd trailing digit is padded with zeros, as `StreamDecoder` does.
+        Ok(HexBits(
+            digits
+                .chunks(2)
+                .map(|pair| {
+                    let byte = (pair[0] << 4) | pair.get(1).copied().unwrap_or(0);
+                    byte.try_into().expect("two hex digits fit in a byte")
+                })
+                .collect(),
+        ))
+    }
+}
+
+impl fmt::Display for HexBits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{:02X}", byte))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LengthType {
+    TotalLength(u16),
+    SubPackets(u16),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperatorType {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
+
+impl TryFrom<u8> for OperatorType {
+    type Error = u8;
+
+    fn try_from(val: u8) -> Result<Self, Self::Error> {
+        Ok(match val {
+            0 => Self::Sum,
+            1 => Self::Product,
+            2 => Self::Minimum,
+            3 => Self::Maximum,
+            5 => Self::GreaterThan,
+            6 => Self::LessThan,
+            7 => Self::EqualTo,
+            _ => return Err(val),
+        })
+    }
+}
+
+impl OperatorType {
+    pub fn type_id(&self) -> u8 {
+        match self {
+            Self::Sum => 0,
+            Self::Product => 1,
+            Self::Minimum => 2,
+            Self::Maximum => 3,
+            Self::GreaterThan => 5,
+            Self::LessThan => 6,
+            Self::EqualTo => 7,
+        }
+    }
+
+    pub fn check_operands(&self, count: usize, offset: usize) -> Option<BitsError> {
+        match self {
+            Self::GreaterThan | Self::LessThan | Self::EqualTo if count != 2 => {
+                Some(BitsError::WrongOperandCount {
+                    op: *self,
+                    expected: 2,
+                    found: count,
+                    offset,
+                })
+            }
+            _ if count == 0 => Some(BitsError::EmptyOperator { op: *self, offset }),
+            _ => None,
+        }
+    }
+
+    pub fn compute<N: BitsNumber>(&self, inputs: &[N], offset: usize) -> Result<N, BitsError> {
+        if let Some(err) = self.check_operands(inputs.len(), offset) {
+            return Err(err);
+        }
+
+        let overflow = BitsError::ArithmeticOverflow { op: *self, offset };
+        let (first, rest) = inputs.split_first().expect("operand count was checked");
+        match self {
+            Self::Sum => rest
+                .iter()
+                .try_fold(first.clone(), |acc, x| acc.checked_add(x)),
+            Self::Product => rest
+                .iter()
+                .try_fold(first.clone(), |acc, x| acc.checked_mul(x)),
+            Self::Minimum => inputs.iter().min().cloned(),
+            Self::Maximum => inputs.iter().max().cloned(),
+            Self::GreaterThan => Some(N::from_bool(inputs[0] > inputs[1])),
+            Self::LessThan => Some(N::from_bool(inputs[0] < inputs[1])),
+            Self::EqualTo => Some(N::from_bool(inputs[0] == inputs[1])),
+        }
+        .ok_or(overflow)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PacketBody {
+    Literal(BigUint),
+    Operator {
+        op: OperatorType,
+        length_type: LengthType,
+        children: Vec<PacketTree>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PacketTree {
+    pub version: u8,
+    pub body: PacketBody,
+}
+
+impl PacketTree {
+    pub fn literal(version: u8, value: u64) -> Self {
+        Self {
+            version,
+            body: PacketBody::Literal(value.into()),
+        }
+    }
+
+    pub fn operator(
+        version: u8,
+        op: OperatorType,
+        length_type: LengthType,
+        children: Vec<PacketTree>,
+    ) -> Self {
+        Self {
+            version,
+            body: PacketBody::Operator {
+                op,
+                length_type,
+                children,
+            },
+        }
+    }
+
+    pub fn decode(hbits: &HexBits) -> Result<Self, BitsError> {
+        let ((stream, bit_offset), tree) = packet(&hbits.0[..])?;
+
+        // Transmissions are zero-padded up to a whole number of hex digits.
+        let end = 8 * (hbits.0.len() - stream.len()) + bit_offset;
+        match (end..(8 * hbits.0.len())).find(|i| hbits.0[i / 8] & (1 << (7 - i % 8)) != 0) {
+            None => Ok(tree),
+            Some(offset) => Err(BitsError::TrailingGarbage { offset }),
+        }
+    }
+
+    pub fn children(&self) -> &[PacketTree] {
+        match &self.body {
+            PacketBody::Literal(_) => &[],
+            PacketBody::Operator { children, .. } => children,
+        }
+    }
+
+    pub fn version_sum(&self) -> usize {
+        self.version as usize
+            + self
+                .children()
+                .iter()
+                .map(PacketTree::version_sum)
+                .sum::<usize>()
+    }
+
+    pub fn bit_len(&self) -> usize {
+        match &self.body {
+            PacketBody::Literal(value) => 6 + 5 * (value.bits() as usize).div_ceil(4).max(1),
+            PacketBody::Operator {
+                length_type,
+                children,
+                ..
+            } => {
+                let field_len = match length_type {
+                    LengthType::TotalLength(_) => 15,
+                    LengthType::SubPackets(_) => 11,
+                };
+                7 + field_len + children.iter().map(PacketTree::bit_len).sum::<usize>()
+            }
+        }
+    }
+
+    fn evaluate_at<N: BitsNumber>(&self, offset: usize) -> Result<(N, usize), BitsError> {
+        let (op, length_type, children) = match &self.body {
+            PacketBody::Literal(value) => {
+                let value = N::from_literal(value).ok_or(BitsError::LiteralOverflow { offset })?;
+                return Ok((value, self.bit_len()));
+            }
+            PacketBody::Operator {
+                op,
+                length_type,
+                children,
+            } => (op, length_type, children),
+        };
+        if let Some(err) = op.check_operands(children.len(), offset) {
+            return Err(err);
+        }
+
+        let mut child_offset = offset
+            + 7
+            + match length_type {
+                LengthType::TotalLength(_) => 15,
+                LengthType::SubPackets(_) => 11,
+            };
+        let mut inputs = Vec::with_capacity(children.len());
+        for child in children {
+            let (value, bit_len) = child.evaluate_at(child_offset)?;
+            inputs.push(value);
+            child_offset += bit_len;
+        }
+
+        Ok((op.compute(&inputs, offset)?, child_offset - offset))
+    }
+
+    // Offsets in evaluation errors refer to the tree's canonical encoding.
+    pub fn evaluate_as<N: BitsNumber>(&self) -> Result<N, BitsError> {
+        self.evaluate_at(0).map(|(value, _)| value)
+    }
+
+    pub fn evaluate(&self) -> Result<u64, BitsError> {
+        self.evaluate_as()
+    }
+
+    pub fn depth(&self) -> usize {
+        1 + self
+            .children()
+            .iter()
+            .map(PacketTree::depth)
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn size(&self) -> usize {
+        1 + self.children().iter().map(PacketTree::size).sum::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest(input, expt_result,
+        case("D2FE28", Ok(HexBits(vec![0xD2, 0xFE, 0x28]))),
+        case("38006F45291200", Ok(HexBits(vec![0x38, 0x00, 0x6F, 0x45, 0x29, 0x12, 0x00]))),
+        case("EE00D40C823060", Ok(HexBits(vec![0xEE, 0x00, 0xD4, 0x0C, 0x82, 0x30, 0x60]))),
+        case("8A004A801A8002F478", Ok(HexBits(vec![0x8A, 0x00, 0x4A, 0x80, 0x1A, 0x80, 0x02, 0xF4, 0x78]))),
+        case("620080001611562C8802118E34", Ok(HexBits(vec![0x62, 0x00, 0x80, 0x00, 0x16, 0x11, 0x56, 0x2C, 0x88, 0x02, 0x11, 0x8E, 0x34]))),
+        case("C0015000016115A2E0802F182340", Ok(HexBits(vec![0xC0, 0x01, 0x50, 0x00, 0x01, 0x61, 0x15, 0xA2, 0xE0, 0x80, 0x2F, 0x18, 0x23, 0x40]))),
+        case("A0016C880162017C3686B18A3D4780", Ok(HexBits(vec![0xA0, 0x01, 0x6C, 0x88, 0x01, 0x62, 0x01, 0x7C, 0x36, 0x86, 0xB1, 0x8A, 0x3D, 0x47, 0x80]))),
+
+        case("D2F", Ok(HexBits(vec![0xD2, 0xF0]))),
+        case("D2FE2", Ok(HexBits(vec![0xD2, 0xFE, 0x20]))),
+        case("DG", Err(BitsError::InvalidDigit { offset: 4 })),
+    )]
+    fn test_hex_decoder_egs(input: &'static str, expt_result: Result<HexBits, BitsError>) {
+        assert_eq!(HexBits::from_str(input), expt_result);
+    }
+
+    #[rstest(
+        input,
+        expt_result,
+        case("D2FE28", 6),
+        case("38006F45291200", 9),
+        case("EE00D40C823060", 14),
+        case("8A004A801A8002F478", 16),
+        case("620080001611562C8802118E34", 12),
+        case("C0015000016115A2E0802F182340", 23),
+        case("A0016C880162017C3686B18A3D4780", 31)
+    )]
+    fn test_packet_tree_version_sum_egs(input: &'static str, expt_result: usize) {
+        let tree = PacketTree::decode(&HexBits::from_str(input).unwrap()).unwrap();
+        assert_eq!(tree.version_sum(), expt_result);
+    }
+
+    #[rstest(
+        input,
+        expt_result,
+        case("D2FE28", PacketTree::literal(6, 2021)),
+        case("38006F45291200", PacketTree::operator(
+            1,
+            OperatorType::LessThan,
+            LengthType::TotalLength(27),
+            vec![PacketTree::literal(6, 10), PacketTree::literal(2, 20)],
+        )),
+        case("EE00D40C823060", PacketTree::operator(
+            7,
+            OperatorType::Maximum,
+            LengthType::SubPackets(3),
+            vec![
+                PacketTree::literal(2, 1),
+                PacketTree::literal(4, 2),
+                PacketTree::literal(1, 3),
+            ],
+        )),
+    )]
+    fn test_packet_tree_decode_egs(input: &'static str, expt_result: PacketTree) {
+        let tree = PacketTree::decode(&HexBits::from_str(input).unwrap()).unwrap();
+        assert_eq!(tree, expt_result);
+    }
+
+    #[rstest(
+        input,
+        expt_result,
+        case("C200B40A82", 3),
+        case("04005AC33890", 54),
+        case("880086C3E88112", 7),
+        case("CE00C43D881120", 9),
+        case("D8005AC2A8F0", 1),
+        case("F600BC2D8F", 0),
+        case("9C005AC2F8F0", 0),
+        case("9C0141080250320F1802104A08", 1)
+    )]
+    fn test_packet_tree_evaluate_egs(input: &'static str, expt_result: u64) {
+        let tree = PacketTree::decode(&HexBits::from_str(input).unwrap()).unwrap();
+        assert_eq!(tree.evaluate(), Ok(expt_result));
+    }
+
+    #[rstest(
+        input,
+        expt_depth,
+        expt_size,
+        case("D2FE28", 1, 1),
+        case("EE00D40C823060", 2, 4),
+        case("8A004A801A8002F478", 4, 4),
+        case("620080001611562C8802118E34", 3, 7)
+    )]
+    fn test_packet_tree_shape_egs(input: &'static str, expt_depth: usize, expt_size: usize) {
+        let tree = PacketTree::decode(&HexBits::from_str(input).unwrap()).unwrap();
+        assert_eq!(tree.depth(), expt_depth);
+        assert_eq!(tree.size(), expt_size);
+    }
+
+    #[rstest(
+        input,
+        expt_result,
+        case("D2", BitsError::Truncated { offset: 7 }),
+        case("D2FE29", BitsError::TrailingGarbage { offset: 23 }),
+        case("D2FE2801", BitsError::TrailingGarbage { offset: 31 }),
+        case("38006B45291200", BitsError::LengthOverflow { offset: 7 }),
+        case("38FF6F45291200", BitsError::LengthOverflow { offset: 7 }),
+        case("0000284080", BitsError::LengthOverflow { offset: 7 }),
+        // the one-operand `EqualTo` sits inside a length-prefixed parent
+        case("0000747801102000", BitsError::WrongOperandCount {
+            op: OperatorType::EqualTo,
+            expected: 2,
+            found: 1,
+            offset: 22
+        })
+    )]
+    fn test_packet_tree_decode_errors(input: &'static str, expt_result: BitsError) {
+        let hbits = HexBits::from_str(input).unwrap();
+        assert_eq!(PacketTree::decode(&hbits), Err(expt_result));
+    }
+
+    #[test]
+    fn test_packet_tree_operand_errors() {
+        use OperatorType::*;
+
+        let op = |op, children| PacketTree::operator(0, op, LengthType::SubPackets(0), children);
+        let lit = |value| PacketTree::literal(0, value);
+
+        let tree = op(Sum, vec![lit(1), op(Minimum, vec![])]);
+        let err = BitsError::EmptyOperator {
+            op: Minimum,
+            offset: 29,
+        };
+        assert_eq!(tree.evaluate(), Err(err));
+        assert_eq!(PacketTree::decode(&tree.encode().unwrap()), Err(err));
+        assert_eq!(
+            err.to_string(),
+            "Minimum operator has no operands at bit 29"
+        );
+
+        let tree = op(EqualTo, vec![lit(1), lit(2), lit(3)]);
+        let err = BitsError::WrongOperandCount {
+            op: EqualTo,
+            expected: 2,
+            found: 3,
+            offset: 0,
+        };
+        assert_eq!(tree.evaluate(), Err(err));
+        assert_eq!(PacketTree::decode(&tree.encode().unwrap()), Err(err));
+
+        assert_eq!(OperatorType::try_from(4), Err(4));
+        assert_eq!(
+            EqualTo.compute(&[1_u64], 5),
+            Err(BitsError::WrongOperandCount {
+                op: EqualTo,
+                expected: 2,
+                found: 1,
+                offset: 5
+            })
+        );
+    }
+}
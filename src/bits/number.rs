@@ -0,0 +1,127 @@
+use num_bigint::BigUint;
+
+use core::convert::TryFrom;
+
+pub trait BitsNumber: Sized + Clone + Ord {
+    fn from_literal(value: &BigUint) -> Option<Self>;
+    fn from_bool(value: bool) -> Self;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+}
+
+macro_rules! impl_bits_number {
+    ($($t:ty),*) => {
+        $(
+            impl BitsNumber for $t {
+                fn from_literal(value: &BigUint) -> Option<Self> {
+                    <$t>::try_from(value).ok()
+                }
+
+                fn from_bool(value: bool) -> Self {
+                    value as $t
+                }
+
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *other)
+                }
+
+                fn checked_mul(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_mul(*self, *other)
+                }
+            }
+        )*
+    };
+}
+
+impl_bits_number!(u64, u128);
+
+impl BitsNumber for BigUint {
+    fn from_literal(value: &BigUint) -> Option<Self> {
+        Some(value.clone())
+    }
+
+    fn from_bool(value: bool) -> Self {
+        BigUint::from(value as u8)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bits::{BitsError, LengthType, OperatorType, PacketBody, PacketTree};
+
+    fn big_literal(hex: &str) -> PacketTree {
+        let value = BigUint::parse_bytes(hex.as_bytes(), 16).unwrap();
+        PacketTree {
+            version: 0,
+            body: PacketBody::Literal(value),
+        }
+    }
+
+    #[test]
+    fn test_oversized_literal() {
+        let tree = big_literal("123456789ABCDEF0123");
+        let decoded = PacketTree::decode(&tree.encode().unwrap()).unwrap();
+        assert_eq!(decoded, tree);
+
+        assert_eq!(
+            decoded.evaluate(),
+            Err(BitsError::LiteralOverflow { offset: 0 })
+        );
+        assert_eq!(decoded.evaluate_as::<u128>(), Ok(0x123456789ABCDEF0123));
+        assert_eq!(
+            decoded.evaluate_as::<BigUint>(),
+            Ok(BigUint::parse_bytes(b"123456789ABCDEF0123", 16).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_arithmetic_overflow() {
+        let product = PacketTree::operator(
+            0,
+            OperatorType::Product,
+            LengthType::SubPackets(0),
+            vec![big_literal("FFFFFFFFFFFF"), big_literal("FFFFFFFFFFFF")],
+        );
+        let tree = PacketTree::operator(
+            0,
+            OperatorType::Maximum,
+            LengthType::SubPackets(0),
+            vec![PacketTree::literal(0, 1), product],
+        );
+
+        assert_eq!(
+            tree.evaluate(),
+            Err(BitsError::ArithmeticOverflow {
+                op: OperatorType::Product,
+                offset: 29
+            })
+        );
+        assert_eq!(
+            tree.evaluate_as::<u128>(),
+            Ok(0xFFFF_FFFF_FFFF * 0xFFFF_FFFF_FFFF)
+        );
+
+        let huge = (0..4).fold(tree, |acc, _| {
+            PacketTree::operator(
+                0,
+                OperatorType::Product,
+                LengthType::TotalLength(0),
+                vec![acc.clone(), acc],
+            )
+        });
+        assert_eq!(
+            huge.evaluate_as::<BigUint>(),
+            Ok(BigUint::from(0xFFFF_FFFF_FFFF_u64).pow(32))
+        );
+        assert!(huge.evaluate_as::<u128>().is_err());
+    }
+}
@@ -0,0 +1,62 @@
+use super::{BitsError, LengthType, OperatorType, PacketBody, PacketTree};
+use crate::nom_utils::BitInput;
+use crate::nom_utils::{bits_remaining, continuation_varint, count_prefixed, length_prefixed};
+
+use core::convert::TryFrom;
+use nom::bits::complete::take as take_bits;
+use nom::combinator::map;
+use num_bigint::BigUint;
+
+type Bits<'a> = BitInput<&'a [u8]>;
+type IResult<'a, O> = nom::IResult<Bits<'a>, O, BitsError>;
+
+// Offsets are counted from the end of the stream until `packet` rebases them.
+fn subpackets(len_type_id: u8, input: Bits) -> IResult<(LengthType, Vec<PacketTree>)> {
+    match len_type_id {
+        0 => map(length_prefixed(15, packet_bits), |(len, children)| {
+            (LengthType::TotalLength(len as u16), children)
+        })(input),
+        _ => map(count_prefixed(11, packet_bits), |(len, children)| {
+            (LengthType::SubPackets(len as u16), children)
+        })(input),
+    }
+}
+
+fn packet_bits(input: Bits) -> IResult<PacketTree> {
+    let packet_offset = bits_remaining(&input);
+    let (input, version): (_, u8) = take_bits(3_usize)(input)?;
+    let type_offset = bits_remaining(&input);
+    let (input, packet_id): (_, u8) = take_bits(3_usize)(input)?;
+
+    if packet_id == 4 {
+        let (input, value): (_, BigUint) = continuation_varint(4)(input)?;
+        let body = PacketBody::Literal(value);
+        return Ok((input, PacketTree { version, body }));
+    }
+
+    let op = OperatorType::try_from(packet_id).map_err(|type_id| {
+        nom::Err::Failure(BitsError::UnknownTypeId {
+            type_id,
+            offset: type_offset,
+        })
+    })?;
+    let (input, len_type_id): (_, u8) = take_bits(1_usize)(input)?;
+    let (input, (length_type, children)) = subpackets(len_type_id, input)?;
+
+    if let Some(err) = op.check_operands(children.len(), packet_offset) {
+        return Err(nom::Err::Failure(err));
+    }
+
+    Ok((
+        input,
+        PacketTree::operator(version, op, length_type, children),
+    ))
+}
+
+pub fn packet(stream: &[u8]) -> Result<(Bits<'_>, PacketTree), BitsError> {
+    let total_bits = 8 * stream.len();
+    packet_bits((stream, 0)).map_err(|err| match err {
+        nom::Err::Incomplete(_) => BitsError::Truncated { offset: total_bits },
+        nom::Err::Error(e) | nom::Err::Failure(e) => e.rebased(total_bits),
+    })
+}
@@ -0,0 +1,199 @@
+use super::{LengthType, OperatorType, PacketBody, PacketTree};
+
+use core::fmt::Write;
+
+impl OperatorType {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Sum => "sum",
+            Self::Product => "product",
+            Self::Minimum => "min",
+            Self::Maximum => "max",
+            Self::GreaterThan => "greater-than",
+            Self::LessThan => "less-than",
+            Self::EqualTo => "equal-to",
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            Self::Sum => "+",
+            Self::Product => "*",
+            Self::Minimum => "min",
+            Self::Maximum => "max",
+            Self::GreaterThan => ">",
+            Self::LessThan => "<",
+            Self::EqualTo => "==",
+        }
+    }
+
+    // `None` marks operators that are rendered in function-call form.
+    fn precedence(&self) -> Option<u8> {
+        match self {
+            Self::GreaterThan | Self::LessThan | Self::EqualTo => Some(1),
+            Self::Sum => Some(2),
+            Self::Product => Some(3),
+            Self::Minimum | Self::Maximum => None,
+        }
+    }
+}
+
+impl PacketTree {
+    fn infix_precedence(&self) -> Option<u8> {
+        match &self.body {
+            PacketBody::Operator { op, children, .. } if children.len() >= 2 => op.precedence(),
+            _ => None,
+        }
+    }
+
+    fn write_pretty(&self, out: &mut String, indent: usize) {
+        write!(out, "{:indent$}[v{}] ", "", self.version, indent = indent).unwrap();
+        match &self.body {
+            PacketBody::Literal(value) => writeln!(out, "literal {} (type 4)", value).unwrap(),
+            PacketBody::Operator {
+                op,
+                length_type,
+                children,
+            } => {
+                let length = match length_type {
+                    LengthType::TotalLength(len) => format!("length type 0: {} bits", len),
+                    LengthType::SubPackets(len) => format!("length type 1: {} sub-packets", len),
+                };
+                writeln!(out, "{} (type {}, {})", op.name(), op.type_id(), length).unwrap();
+                for child in children {
+                    child.write_pretty(out, indent + 2);
+                }
+            }
+        }
+    }
+
+    pub fn pretty(&self) -> String {
+        let mut out = String::new();
+        self.write_pretty(&mut out, 0);
+        out
+    }
+
+    pub fn to_infix(&self) -> String {
+        let (op, children) = match &self.body {
+            PacketBody::Literal(value) => return value.to_string(),
+            PacketBody::Operator { op, children, .. } => (op, children),
+        };
+
+        match self.infix_precedence() {
+            None => {
+                let name = match op.precedence() {
+                    Some(_) => op.name(),
+                    None => op.symbol(),
+                };
+                let args = children
+                    .iter()
+                    .map(PacketTree::to_infix)
+                    .collect::<Vec<_>>();
+                format!("{}({})", name, args.join(", "))
+            }
+            Some(precedence) => {
+                let operands = children
+                    .iter()
+                    .map(|child| match child.infix_precedence() {
+                        Some(p) if p <= precedence => format!("({})", child.to_infix()),
+                        _ => child.to_infix(),
+                    })
+                    .collect::<Vec<_>>();
+                operands.join(&format!(" {} ", op.symbol()))
+            }
+        }
+    }
+
+    pub fn to_sexpr(&self) -> String {
+        match &self.body {
+            PacketBody::Literal(value) => value.to_string(),
+            PacketBody::Operator { op, children, .. } => {
+                let mut out = format!("({}", op.symbol());
+                for child in children {
+                    write!(out, " {}", child.to_sexpr()).unwrap();
+                }
+                out.push(')');
+                out
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bits::HexBits;
+    use core::str::FromStr;
+    use rstest::*;
+
+    fn decode(input: &str) -> PacketTree {
+        PacketTree::decode(&HexBits::from_str(input).unwrap()).unwrap()
+    }
+
+    #[rstest(
+        input,
+        expt_infix,
+        expt_sexpr,
+        case("C200B40A82", "1 + 2", "(+ 1 2)"),
+        case("04005AC33890", "6 * 9", "(* 6 9)"),
+        case("880086C3E88112", "min(7, 8, 9)", "(min 7 8 9)"),
+        case("CE00C43D881120", "max(7, 8, 9)", "(max 7 8 9)"),
+        case("D8005AC2A8F0", "5 < 15", "(< 5 15)"),
+        case("F600BC2D8F", "5 > 15", "(> 5 15)"),
+        case("9C005AC2F8F0", "5 == 15", "(== 5 15)"),
+        case("9C0141080250320F1802104A08", "1 + 3 == 2 * 2", "(== (+ 1 3) (* 2 2))"),
+        case("8A004A801A8002F478", "min(min(min(15)))", "(min (min (min 15)))"),
+        case("D2FE28", "2021", "2021")
+    )]
+    fn test_render_egs(input: &'static str, expt_infix: &str, expt_sexpr: &str) {
+        let tree = decode(input);
+        assert_eq!(tree.to_infix(), expt_infix);
+        assert_eq!(tree.to_sexpr(), expt_sexpr);
+    }
+
+    #[test]
+    fn test_render_infix_nesting() {
+        use OperatorType::*;
+
+        let op = |op, children| PacketTree::operator(0, op, LengthType::SubPackets(0), children);
+        let lit = |value| PacketTree::literal(0, value);
+
+        let tree = op(
+            Sum,
+            vec![
+                op(Maximum, vec![lit(1), lit(2), lit(3)]),
+                op(LessThan, vec![lit(10), lit(20)]),
+            ],
+        );
+        assert_eq!(tree.to_infix(), "max(1, 2, 3) + (10 < 20)");
+
+        let tree = op(
+            Sum,
+            vec![
+                op(Product, vec![lit(2), lit(3)]),
+                op(Sum, vec![lit(4), lit(5)]),
+            ],
+        );
+        assert_eq!(tree.to_infix(), "2 * 3 + (4 + 5)");
+
+        let tree = op(Product, vec![op(Sum, vec![lit(1)]), lit(2)]);
+        assert_eq!(tree.to_infix(), "sum(1) * 2");
+    }
+
+    #[test]
+    fn test_pretty() {
+        assert_eq!(
+            decode("38006F45291200").pretty(),
+            "[v1] less-than (type 6, length type 0: 27 bits)\n  \
+               [v6] literal 10 (type 4)\n  \
+               [v2] literal 20 (type 4)\n"
+        );
+        assert_eq!(
+            decode("EE00D40C823060").pretty(),
+            "[v7] max (type 3, length type 1: 3 sub-packets)\n  \
+               [v2] literal 1 (type 4)\n  \
+               [v4] literal 2 (type 4)\n  \
+               [v1] literal 3 (type 4)\n"
+        );
+    }
+}
@@ -0,0 +1,586 @@
+use super::{BitsError, BitsNumber, LengthType, OperatorType};
+use crate::nom_utils::{self, bits_remaining, consumed_bits, streaming, BitInput};
+
+use core::convert::TryFrom;
+use nom::error::ErrorKind;
+use num_bigint::BigUint;
+use std::io::Read;
+
+const CHUNK_SIZE: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Hex,
+    Raw,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    Literal {
+        version: u8,
+        value: BigUint,
+        offset: usize,
+    },
+    Operator {
+        version: u8,
+        op: OperatorType,
+        length_type: LengthType,
+        offset: usize,
+    },
+    End,
+}
+
+type Bits<'a> = BitInput<&'a [u8]>;
+type PResult<'a, O> = nom::IResult<Bits<'a>, O, nom::error::Error<Bits<'a>>>;
+
+// A field is parsed with the streaming combinators until the reader is
+// exhausted, then once more with the complete ones so that a truncated field is
+// reported where it actually breaks off.
+type Field<O> = for<'a> fn(Bits<'a>, bool, usize) -> PResult<'a, O>;
+
+fn bits_field(input: Bits<'_>, complete: bool, bitlen: usize) -> PResult<'_, u64> {
+    if complete {
+        nom::bits::complete::take(bitlen)(input)
+    } else {
+        nom::bits::streaming::take(bitlen)(input)
+    }
+}
+
+fn literal_field(input: Bits<'_>, complete: bool, _: usize) -> PResult<'_, BigUint> {
+    if complete {
+        nom_utils::continuation_varint(4)(input)
+    } else {
+        streaming::continuation_varint(4)(input)
+    }
+}
+
+#[derive(Debug)]
+enum Remaining {
+    Bits { end: usize, field_offset: usize },
+    Packets(usize),
+}
+
+#[derive(Debug)]
+struct Frame {
+    op: OperatorType,
+    offset: usize,
+    children: usize,
+    remaining: Remaining,
+}
+
+pub struct StreamDecoder<R> {
+    reader: R,
+    encoding: Encoding,
+    bytes: Vec<u8>,
+    start: usize,
+    bit_offset: usize,
+    pending_nibble: Option<u8>,
+    eof: bool,
+    position: usize,
+    stack: Vec<Frame>,
+    started: bool,
+    done: bool,
+}
+
+impl<R: Read> StreamDecoder<R> {
+    pub fn new(reader: R, encoding: Encoding) -> Self {
+        Self {
+            reader,
+            encoding,
+            bytes: Vec::new(),
+            start: 0,
+            bit_offset: 0,
+            pending_nibble: None,
+            eof: false,
+            position: 0,
+            stack: Vec::new(),
+            started: false,
+            done: false,
+        }
+    }
+
+    pub fn from_hex(reader: R) -> Self {
+        Self::new(reader, Encoding::Hex)
+    }
+
+    pub fn from_raw(reader: R) -> Self {
+        Self::new(reader, Encoding::Raw)
+    }
+
+    fn input(&self) -> BitInput<&[u8]> {
+        (&self.bytes[self.start..], self.bit_offset)
+    }
+
+    // Returns `false` once the reader is exhausted.
+    fn fill(&mut self) -> Result<bool, BitsError> {
+        if self.eof {
+            return Ok(false);
+        }
+        self.bytes.drain(..self.start);
+        self.start = 0;
+
+        let mut chunk = [0; CHUNK_SIZE];
+        let read_len = loop {
+            match self.reader.read(&mut chunk) {
+                Ok(n) => break n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    return Err(BitsError::Io {
+                        kind: e.kind(),
+                        offset: self.position,
+                    })
+                }
+            }
+        };
+        if read_len == 0 {
+            self.eof = true;
+            // An odd trailing hex digit still carries four bits of data.
+            if let Some(nibble) = self.pending_nibble.take() {
+                self.bytes.push(nibble << 4);
+                return Ok(true);
+            }
+            return Ok(false);
+        }
+
+        match self.encoding {
+            Encoding::Raw => self.bytes.extend_from_slice(&chunk[..read_len]),
+            Encoding::Hex => {
+                for &c in &chunk[..read_len] {
+                    if c.is_ascii_whitespace() {
+                        continue;
+                    }
+                    let digit = (c as char).to_digit(16).ok_or(BitsError::InvalidDigit {
+                        offset: 8 * (self.bytes.len() - self.start) - self.bit_offset
+                            + self.position
+                            + 4 * self.pending_nibble.is_some() as usize,
+                    })? as u8;
+                    match self.pending_nibble.take() {
+                        Some(high) => self.bytes.push((high << 4) | digit),
+                        None => self.pending_nibble = Some(digit),
+                    }
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    fn parse<O>(&mut self, field: Field<O>, arg: usize) -> Result<O, BitsError> {
+        loop {
+            let input = self.input();
+            let before = bits_remaining(&input);
+            let result = match consumed_bits(|input| field(input, self.eof, arg))(input) {
+                Ok((_, (value, consumed))) => Ok((consumed, value)),
+                Err(nom::Err::Incomplete(_)) => Err(None),
+                Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => {
+                    Err(Some((before - bits_remaining(&e.input), e.code)))
+                }
+            };
+
+            match result {
+                Ok((consumed, value)) => {
+                    let end = 8 * self.start + self.bit_offset + consumed;
+                    self.start = end / 8;
+                    self.bit_offset = end % 8;
+                    self.position += consumed;
+                    return Ok(value);
+                }
+                Err(None) => {
+                    self.fill()?;
+                }
+                Err(Some((_, ErrorKind::TooLarge))) => {
+                    return Err(BitsError::LiteralOverflow {
+                        offset: self.position,
+                    })
+                }
+                Err(Some((at, _))) => {
+                    return Err(BitsError::Truncated {
+                        offset: self.position + at,
+                    })
+                }
+            }
+        }
+    }
+
+    fn take(&mut self, bitlen: usize) -> Result<u64, BitsError> {
+        self.parse(bits_field, bitlen)
+    }
+
+    fn finish(&mut self) -> Result<(), BitsError> {
+        let result: PResult<_> = streaming::take_rem()(self.input());
+        let ((rest, _), (bits, bitlen)) = result.expect("the current byte is buffered");
+        if bits != 0 {
+            let offset = self.position + bits.leading_zeros() as usize - (8 - bitlen);
+            return Err(BitsError::TrailingGarbage { offset });
+        }
+        self.start = self.bytes.len() - rest.len();
+        self.bit_offset = 0;
+        self.position += bitlen;
+
+        loop {
+            let remaining = &self.bytes[self.start..];
+            if let Some(i) = remaining.iter().position(|&b| b != 0) {
+                let offset = self.position + 8 * i + remaining[i].leading_zeros() as usize;
+                return Err(BitsError::TrailingGarbage { offset });
+            }
+            self.position += 8 * remaining.len();
+            self.start = self.bytes.len();
+            if !self.fill()? {
+                return Ok(());
+            }
+        }
+    }
+
+    fn read_packet(&mut self) -> Result<Event, BitsError> {
+        let offset = self.position;
+        if let Some(frame) = self.stack.last_mut() {
+            frame.children += 1;
+            if let Remaining::Packets(n) = &mut frame.remaining {
+                *n -= 1;
+            }
+        }
+
+        let version = self.take(3)? as u8;
+        let type_offset = self.position;
+        let type_id = self.take(3)? as u8;
+
+        if type_id == 4 {
+            let value = self.parse(literal_field, 0)?;
+            return Ok(Event::Literal {
+                version,
+                value,
+                offset,
+            });
+        }
+
+        let op = OperatorType::try_from(type_id).map_err(|type_id| BitsError::UnknownTypeId {
+            type_id,
+            offset: type_offset,
+        })?;
+        let (length_type, remaining) = match self.take(1)? {
+            0 => {
+                let field_offset = self.position;
+                let len = self.take(15)? as u16;
+                let end = self.position + len as usize;
+                (
+                    LengthType::TotalLength(len),
+                    Remaining::Bits { end, field_offset },
+                )
+            }
+            _ => {
+                let len = self.take(11)? as u16;
+                (
+                    LengthType::SubPackets(len),
+                    Remaining::Packets(len as usize),
+                )
+            }
+        };
+        self.stack.push(Frame {
+            op,
+            offset,
+            children: 0,
+            remaining,
+        });
+
+        Ok(Event::Operator {
+            version,
+            op,
+            length_type,
+            offset,
+        })
+    }
+
+    fn next_event(&mut self) -> Result<Option<Event>, BitsError> {
+        let frame = match self.stack.last() {
+            None if self.started => {
+                self.finish()?;
+                return Ok(None);
+            }
+            None => {
+                self.started = true;
+                return self.read_packet().map(Some);
+            }
+            Some(frame) => frame,
+        };
+
+        let frame_done = match frame.remaining {
+            Remaining::Bits { end, field_offset } if self.position > end => {
+                return Err(BitsError::LengthOverflow {
+                    offset: field_offset,
+                })
+            }
+            Remaining::Bits { end, .. } => self.position == end,
+            Remaining::Packets(n) => n == 0,
+        };
+        if !frame_done {
+            return self.read_packet().map(Some);
+        }
+
+        let frame = self.stack.pop().expect("stack is non-empty");
+        match frame.op.check_operands(frame.children, frame.offset) {
+            Some(err) => Err(err),
+            None => Ok(Some(Event::End)),
+        }
+    }
+
+    pub fn version_sum(self) -> Result<usize, BitsError> {
+        let mut total = 0;
+        for event in self {
+            match event? {
+                Event::Literal { version, .. } | Event::Operator { version, .. } => {
+                    total += version as usize
+                }
+                Event::End => (),
+            }
+        }
+        Ok(total)
+    }
+
+    pub fn evaluate<N: BitsNumber>(self) -> Result<N, BitsError> {
+        let mut partials: Vec<Partial<N>> = Vec::new();
+        let mut result = None;
+
+        for event in self {
+            let value = match event? {
+                Event::Literal { value, offset, .. } => {
+                    N::from_literal(&value).ok_or(BitsError::LiteralOverflow { offset })?
+                }
+                Event::Operator { op, offset, .. } => {
+                    partials.push(Partial {
+                        op,
+                        offset,
+                        count: 0,
+                        values: Vec::with_capacity(2),
+                    });
+                    continue;
+                }
+                Event::End => {
+                    let partial = partials.pop().expect("events are balanced");
+                    partial.op.compute(&partial.values, partial.offset)?
+                }
+            };
+            match partials.last_mut() {
+                Some(parent) => parent.push(value)?,
+                None => result = Some(value),
+            }
+        }
+
+        Ok(result.expect("a successful stream contains a packet"))
+    }
+}
+
+struct Partial<N> {
+    op: OperatorType,
+    offset: usize,
+    count: usize,
+    values: Vec<N>,
+}
+
+impl<N: BitsNumber> Partial<N> {
+    // Associative operators are folded as operands arrive, so no more than two
+    // values are ever held per open operator.
+    fn push(&mut self, value: N) -> Result<(), BitsError> {
+        self.count += 1;
+        match self.op {
+            OperatorType::GreaterThan | OperatorType::LessThan | OperatorType::EqualTo => {
+                if self.count > 2 {
+                    return Err(BitsError::WrongOperandCount {
+                        op: self.op,
+                        expected: 2,
+                        found: self.count,
+                        offset: self.offset,
+                    });
+                }
+                self.values.push(value);
+            }
+            _ => {
+                self.values.push(value);
+                if self.values.len() == 2 {
+                    let folded = self.op.compute(&self.values, self.offset)?;
+                    self.values.clear();
+                    self.values.push(folded);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for StreamDecoder<R> {
+    type Item = Result<Event, BitsError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = self.next_event();
+        if !matches!(result, Ok(Some(_))) {
+            self.done = true;
+        }
+        result.transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bits::{HexBits, PacketTree};
+    use core::str::FromStr;
+    use rstest::*;
+
+    struct ChunkedReader<'a> {
+        data: &'a [u8],
+        step: usize,
+    }
+
+    impl Read for ChunkedReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let len = self.step.min(buf.len()).min(self.data.len());
+            buf[..len].copy_from_slice(&self.data[..len]);
+            self.data = &self.data[len..];
+            Ok(len)
+        }
+    }
+
+    fn decoder(input: &str, step: usize) -> StreamDecoder<ChunkedReader<'_>> {
+        StreamDecoder::from_hex(ChunkedReader {
+            data: input.as_bytes(),
+            step,
+        })
+    }
+
+    #[rstest(
+        input,
+        case("D2FE28"),
+        case("D2FE2"),
+        case("8A004A801A8002F478"),
+        case("620080001611562C8802118E34"),
+        case("C0015000016115A2E0802F182340"),
+        case("A0016C880162017C3686B18A3D4780"),
+        case("C200B40A82"),
+        case("04005AC33890"),
+        case("880086C3E88112"),
+        case("CE00C43D881120"),
+        case("D8005AC2A8F0"),
+        case("F600BC2D8F"),
+        case("9C005AC2F8F0"),
+        case("9C0141080250320F1802104A08")
+    )]
+    fn test_stream_matches_tree(input: &'static str) {
+        let hbits = HexBits::from_str(input).unwrap();
+        let tree = PacketTree::decode(&hbits).unwrap();
+
+        for step in [1, 3, CHUNK_SIZE] {
+            assert_eq!(decoder(input, step).version_sum(), Ok(tree.version_sum()));
+            assert_eq!(decoder(input, step).evaluate::<u64>(), tree.evaluate());
+        }
+        let raw = StreamDecoder::from_raw(&hbits.0[..]);
+        assert_eq!(raw.evaluate::<u64>(), tree.evaluate());
+    }
+
+    #[test]
+    fn test_stream_events() {
+        let events = decoder("38006F45291200\n", 2)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            events,
+            vec![
+                Event::Operator {
+                    version: 1,
+                    op: OperatorType::LessThan,
+                    length_type: LengthType::TotalLength(27),
+                    offset: 0,
+                },
+                Event::Literal {
+                    version: 6,
+                    value: BigUint::from(10_u8),
+                    offset: 22,
+                },
+                Event::Literal {
+                    version: 2,
+                    value: BigUint::from(20_u8),
+                    offset: 33,
+                },
+                Event::End,
+            ]
+        );
+    }
+
+    #[rstest(
+        input,
+        expt_result,
+        case("D2", BitsError::Truncated { offset: 7 }),
+        case("D2FE29", BitsError::TrailingGarbage { offset: 23 }),
+        case("D2FE2801", BitsError::TrailingGarbage { offset: 31 }),
+        case("D2FG", BitsError::InvalidDigit { offset: 12 }),
+        case("38006B45291200", BitsError::LengthOverflow { offset: 7 }),
+        case("0000284080", BitsError::LengthOverflow { offset: 7 }),
+        case("0000747801102000", BitsError::WrongOperandCount {
+            op: OperatorType::EqualTo,
+            expected: 2,
+            found: 1,
+            offset: 22
+        })
+    )]
+    fn test_stream_errors(input: &'static str, expt_result: BitsError) {
+        assert_eq!(decoder(input, 1).evaluate::<u64>(), Err(expt_result));
+    }
+
+    #[test]
+    fn test_stream_long_literal() {
+        // a 30-group literal, re-parsed from its start as each byte arrives
+        let mut bits = format!("000100{}01111", "11111".repeat(29));
+        while bits.len() % 8 != 0 {
+            bits.push('0');
+        }
+        let hex = bits
+            .as_bytes()
+            .chunks(8)
+            .map(|byte| {
+                let byte = u8::from_str_radix(core::str::from_utf8(byte).unwrap(), 2).unwrap();
+                format!("{:02X}", byte)
+            })
+            .collect::<String>();
+
+        let events = decoder(&hex, 1).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(
+            events,
+            vec![Event::Literal {
+                version: 0,
+                value: (BigUint::from(1_u8) << 120_u32) - 1_u8,
+                offset: 0,
+            }]
+        );
+        assert_eq!(
+            decoder(&hex, 1).evaluate::<u64>(),
+            Err(BitsError::LiteralOverflow { offset: 0 })
+        );
+        assert_eq!(
+            decoder(&hex[..20], 1).evaluate::<u64>(),
+            Err(BitsError::Truncated { offset: 77 })
+        );
+    }
+
+    #[test]
+    fn test_stream_deep_transmission() {
+        // A chain of single-operand sums, far too deep to decode recursively.
+        let depth = 100_000;
+        let mut bits = "000000100000000001".repeat(depth);
+        bits.push_str("00010000111");
+        while bits.len() % 8 != 0 {
+            bits.push('0');
+        }
+        let hex = bits
+            .as_bytes()
+            .chunks(8)
+            .map(|byte| {
+                let byte = u8::from_str_radix(core::str::from_utf8(byte).unwrap(), 2).unwrap();
+                format!("{:02X}", byte)
+            })
+            .collect::<String>();
+
+        assert_eq!(decoder(&hex, CHUNK_SIZE).evaluate::<u64>(), Ok(7));
+        assert_eq!(decoder(&hex, CHUNK_SIZE).version_sum(), Ok(0));
+        assert_eq!(decoder(&hex, CHUNK_SIZE).count(), 2 * depth + 1);
+    }
+}
@@ -0,0 +1,367 @@
+use crate::parse::ParseError;
+
+use core::fmt;
+use core::hash::Hash;
+use core::ops::{BitOr, Not, Shl};
+use core::str::FromStr;
+
+pub trait BitStorage: Copy + Eq + Hash + fmt::Debug {
+    const BITS: usize;
+
+    fn zero() -> Self;
+    fn bit(&self, pos: usize) -> bool;
+    fn set_bit(&mut self, pos: usize, value: bool);
+    fn count_ones(&self) -> u32;
+    fn and(self, other: Self) -> Self;
+    fn xor(self, other: Self) -> Self;
+    // The lowest `n` bits set, saturating at `BITS`.
+    fn low_mask(n: usize) -> Self;
+}
+
+macro_rules! impl_bit_storage {
+    ($($t:ty),*) => {
+        $(
+            impl BitStorage for $t {
+                const BITS: usize = <$t>::BITS as usize;
+
+                fn zero() -> Self {
+                    0
+                }
+
+                fn bit(&self, pos: usize) -> bool {
+                    (self >> pos) & 1 == 1
+                }
+
+                fn set_bit(&mut self, pos: usize, value: bool) {
+                    *self = (*self & !(1 << pos)) | ((value as $t) << pos);
+                }
+
+                fn count_ones(&self) -> u32 {
+                    <$t>::count_ones(*self)
+                }
+
+                fn and(self, other: Self) -> Self {
+                    self & other
+                }
+
+                fn xor(self, other: Self) -> Self {
+                    self ^ other
+                }
+
+                fn low_mask(n: usize) -> Self {
+                    if n >= <Self as BitStorage>::BITS {
+                        <$t>::MAX
+                    } else {
+                        (1 << n) - 1
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_bit_storage!(u32, u64, u128);
+
+impl<const K: usize> BitStorage for [u64; K] {
+    const BITS: usize = 64 * K;
+
+    fn zero() -> Self {
+        [0; K]
+    }
+
+    fn bit(&self, pos: usize) -> bool {
+        self[pos / 64].bit(pos % 64)
+    }
+
+    fn set_bit(&mut self, pos: usize, value: bool) {
+        self[pos / 64].set_bit(pos % 64, value)
+    }
+
+    fn count_ones(&self) -> u32 {
+        self.iter().map(|word| word.count_ones()).sum()
+    }
+
+    fn and(self, other: Self) -> Self {
+        core::array::from_fn(|i| self[i] & other[i])
+    }
+
+    fn xor(self, other: Self) -> Self {
+        core::array::from_fn(|i| self[i] ^ other[i])
+    }
+
+    fn low_mask(n: usize) -> Self {
+        core::array::from_fn(|i| u64::low_mask(n.saturating_sub(64 * i)))
+    }
+}
+
+pub trait Unsigned:
+    Copy + Default + From<bool> + Shl<u32, Output = Self> + BitOr<Output = Self>
+{
+    const BITS: u32;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl Unsigned for $t {
+                const BITS: u32 = <$t>::BITS;
+            }
+        )*
+    };
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+
+// Column 0 is the leftmost (most significant) bit, as the bits are written in
+// the puzzle input; it is stored at position `len - 1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitVector<S: BitStorage = u64> {
+    bits: S,
+    len: usize,
+}
+
+impl<S: BitStorage> BitVector<S> {
+    pub fn zeros(len: usize) -> Option<Self> {
+        (len <= S::BITS).then(|| Self {
+            bits: S::zero(),
+            len,
+        })
+    }
+
+    pub fn from_bools<I: IntoIterator<Item = bool>>(bools: I) -> Option<Self> {
+        let bools: Vec<bool> = bools.into_iter().collect();
+        let mut result = Self::zeros(bools.len())?;
+        for (col, bit) in bools.into_iter().enumerate() {
+            result.set(col, bit);
+        }
+        Some(result)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn pos(&self, col: usize) -> usize {
+        assert!(
+            col < self.len,
+            "column {} out of range for width {}",
+            col,
+            self.len
+        );
+        self.len - 1 - col
+    }
+
+    pub fn get(&self, col: usize) -> bool {
+        self.bits.bit(self.pos(col))
+    }
+
+    pub fn set(&mut self, col: usize, value: bool) {
+        let pos = self.pos(col);
+        self.bits.set_bit(pos, value);
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(move |col| self.get(col))
+    }
+
+    pub fn to_uint<U: Unsigned>(&self) -> Option<U> {
+        let significant = self.iter().skip_while(|bit| !bit).count();
+        (significant <= U::BITS as usize).then(|| {
+            self.iter()
+                .skip(self.len - significant)
+                .fold(U::default(), |acc, bit| (acc << 1) | U::from(bit))
+        })
+    }
+
+    pub fn starts_with(&self, prefix: &Self, n: usize) -> bool {
+        assert!(
+            n <= self.len && n <= prefix.len,
+            "prefix longer than vector"
+        );
+        if self.len != prefix.len {
+            return (0..n).all(|col| self.get(col) == prefix.get(col));
+        }
+
+        let mask = S::low_mask(self.len).xor(S::low_mask(self.len - n));
+        self.bits.xor(prefix.bits).and(mask) == S::zero()
+    }
+
+    pub fn column_counts(vectors: &[Self]) -> Vec<usize> {
+        let width = vectors.iter().map(Self::len).max().unwrap_or(0);
+        let mut counts = vec![0; width];
+        for v in vectors {
+            for (count, bit) in counts.iter_mut().zip(v.iter()) {
+                *count += bit as usize;
+            }
+        }
+        counts
+    }
+
+    // The most common bit in each column, with `tie` used for evenly split
+    // columns. `None` if the vectors are empty or of differing widths.
+    pub fn majority(vectors: &[Self], tie: bool) -> Option<Self> {
+        Self::by_column(vectors, true, tie)
+    }
+
+    pub fn minority(vectors: &[Self], tie: bool) -> Option<Self> {
+        Self::by_column(vectors, false, tie)
+    }
+
+    fn by_column(vectors: &[Self], most_common: bool, tie: bool) -> Option<Self> {
+        let width = vectors.first()?.len;
+        if vectors.iter().any(|v| v.len != width) {
+            return None;
+        }
+
+        let total = vectors.len();
+        Self::from_bools(Self::column_counts(vectors).into_iter().map(|ones| {
+            match (2 * ones).cmp(&total) {
+                core::cmp::Ordering::Equal => tie,
+                ord => (ord == core::cmp::Ordering::Greater) == most_common,
+            }
+        }))
+    }
+
+    pub fn filter_prefix<'a>(
+        vectors: &'a [Self],
+        prefix: &'a Self,
+        n: usize,
+    ) -> impl Iterator<Item = &'a Self> + 'a {
+        vectors.iter().filter(move |v| v.starts_with(prefix, n))
+    }
+}
+
+impl<S: BitStorage> Not for BitVector<S> {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self {
+            bits: self.bits.xor(S::low_mask(self.len)),
+            len: self.len,
+        }
+    }
+}
+
+impl<S: BitStorage> FromStr for BitVector<S> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((i, _)) = s.char_indices().nth(S::BITS) {
+            let expected = format!("at most {} bits", S::BITS);
+            return Err(ParseError::at(s, &s[i..], expected));
+        }
+
+        let mut result = Self {
+            bits: S::zero(),
+            len: s.chars().count(),
+        };
+        for (col, (i, c)) in s.char_indices().enumerate() {
+            match c {
+                '0' => result.set(col, false),
+                '1' => result.set(col, true),
+                _ => return Err(ParseError::at(s, &s[i..], "'0' or '1'")),
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl<S: BitStorage> fmt::Display for BitVector<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for bit in self.iter() {
+            write!(f, "{}", bit as u8)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [&str; 12] = [
+        "00100", "11110", "10110", "10111", "10101", "01111", "00111", "11100", "10000", "11001",
+        "00010", "01010",
+    ];
+
+    fn example<S: BitStorage>() -> Vec<BitVector<S>> {
+        EXAMPLE.iter().map(|s| s.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_parse_and_convert() {
+        let v: BitVector = "10110".parse().unwrap();
+        assert_eq!((v.len(), v.count_ones()), (5, 3));
+        assert_eq!(v.to_string(), "10110");
+        assert_eq!(v.to_uint::<u8>(), Some(22));
+        assert_eq!((!v).to_uint::<u8>(), Some(9));
+
+        let wide: BitVector<[u64; 2]> = format!("1{}", "0".repeat(99)).parse().unwrap();
+        assert_eq!(wide.to_uint::<u128>(), Some(1 << 99));
+        assert_eq!(wide.to_uint::<u64>(), None);
+        assert_eq!((!wide).count_ones(), 99);
+
+        let padded: BitVector<u128> = format!("{}11", "0".repeat(70)).parse().unwrap();
+        assert_eq!(padded.to_uint::<u8>(), Some(3));
+
+        let err = "1".repeat(65).parse::<BitVector>().unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (65, "at most 64 bits"));
+        let err = "10201".parse::<BitVector>().unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (3, "'0' or '1'"));
+    }
+
+    #[test]
+    fn test_columns() {
+        let report = example::<u64>();
+        assert_eq!(BitVector::column_counts(&report), vec![7, 5, 8, 7, 5]);
+
+        let gamma = BitVector::majority(&report, true).unwrap();
+        let epsilon = BitVector::minority(&report, false).unwrap();
+        assert_eq!(gamma.to_uint::<u32>(), Some(22));
+        assert_eq!(epsilon.to_uint::<u32>(), Some(9));
+
+        let ragged = vec![gamma, "1".parse().unwrap()];
+        assert_eq!(BitVector::majority(&ragged, true), None);
+        assert_eq!(BitVector::<u64>::majority(&[], true), None);
+    }
+
+    fn rating<S: BitStorage>(report: &[BitVector<S>], most_common: bool) -> Option<u32> {
+        let mut prefix = BitVector::zeros(report[0].len())?;
+        for col in 0..prefix.len() {
+            let candidates: Vec<_> = BitVector::filter_prefix(report, &prefix, col)
+                .copied()
+                .collect();
+            if let [last] = candidates[..] {
+                return last.to_uint();
+            }
+            let target = BitVector::by_column(&candidates, most_common, most_common)?;
+            prefix.set(col, target.get(col));
+        }
+        prefix.to_uint()
+    }
+
+    #[test]
+    fn test_prefix_filter() {
+        let report = example::<u128>();
+        assert_eq!(rating(&report, true), Some(23));
+        assert_eq!(rating(&report, false), Some(10));
+
+        let report = example::<[u64; 3]>();
+        assert_eq!(rating(&report, true), Some(23));
+
+        let a: BitVector = "10110".parse().unwrap();
+        let b: BitVector = "10011".parse().unwrap();
+        assert!(a.starts_with(&b, 2));
+        assert!(!a.starts_with(&b, 3));
+        assert!(a.starts_with(&b, 0));
+    }
+}
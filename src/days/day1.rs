@@ -0,0 +1,70 @@
+use crate::solver::Solver;
+use crate::utils::{parsing_input_strict, LineError};
+
+use itertools::Itertools;
+
+use std::cmp::Ord;
+use std::ops::{Add, Sub};
+
+pub fn count_diffs<I, T>(seq: I) -> usize
+where
+    T: Copy + Ord + Sub<Output = T> + Default,
+    I: Iterator<Item = T>,
+{
+    seq.tuple_windows()
+        .map(|(v1, v2)| v2 - v1)
+        .filter(|v| v > &T::default())
+        .count()
+}
+
+pub fn count_window_diffs<I, T, U>(seq: I) -> usize
+where
+    T: Copy + Add<Output = T> + Sub<Output = U>,
+    U: Copy + Ord + Default,
+    I: Iterator<Item = T>,
+{
+    seq.tuple_windows()
+        .map(|(v1, v2, v3)| v1 + v2 + v3)
+        .tuple_windows()
+        .map(|(v1, v2)| v2 - v1)
+        .filter(|v| v > &U::default())
+        .count()
+}
+
+pub struct Day1;
+
+impl Solver for Day1 {
+    type Input = Vec<i32>;
+    type Error = LineError;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        parsing_input_strict(input.as_bytes()).collect()
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Ok(count_diffs(input.iter().copied()))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        Ok(count_window_diffs(input.iter().copied()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_diffs() {
+        let sequence = vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+        assert_eq!(count_diffs(sequence.into_iter()), 7_usize);
+    }
+
+    #[test]
+    fn test_count_window_diffs() {
+        let sequence = vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+        assert_eq!(count_window_diffs(sequence.into_iter()), 5_usize);
+    }
+}
@@ -1,23 +1,30 @@
-use aoc_lib::utils::parsing_input;
+use crate::solver::Solver;
+use crate::utils::{parsing_input_strict, AnyError};
 
 #[derive(Debug, PartialEq, Eq)]
-enum Bracket {
+pub enum Bracket {
     Parenthesis,
     Square,
     Curly,
     Angle,
 }
 
-struct ErrorTracker {
+pub struct ErrorTracker {
     stack: Vec<Bracket>,
 }
 
+impl Default for ErrorTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ErrorTracker {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self { stack: Vec::new() }
     }
 
-    fn advance(&mut self, c: char) -> Result<Option<Bracket>, &'static str> {
+    pub fn advance(&mut self, c: char) -> Result<Option<Bracket>, &'static str> {
         use Bracket::*;
         let (bracket, is_closing) = match c {
             '(' => Ok((Parenthesis, false)),
@@ -35,7 +42,7 @@ impl ErrorTracker {
 
         Ok(if is_closing {
             if let Some(open_bracket) = self.stack.pop() {
-                (bracket != open_bracket).then(|| bracket)
+                (bracket != open_bracket).then_some(bracket)
             } else {
                 Some(bracket)
             }
@@ -46,7 +53,7 @@ impl ErrorTracker {
     }
 }
 
-fn syntax_score(s: &str) -> Result<(u64, u64), &'static str> {
+pub fn syntax_score(s: &str) -> Result<(u64, u64), &'static str> {
     let mut error_tracker = ErrorTracker::new();
 
     let mut error_score = 0;
@@ -78,6 +85,45 @@ fn syntax_score(s: &str) -> Result<(u64, u64), &'static str> {
     Ok((autocomplete_score, error_score))
 }
 
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Input = Vec<String>;
+    type Error = AnyError;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        let lines = parsing_input_strict(input.as_bytes()).collect::<Result<_, _>>()?;
+        Ok(lines)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        let mut error_score = 0;
+        for s in input {
+            let (_auto, error) = syntax_score(s)?;
+            error_score += error;
+        }
+
+        Ok(error_score)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        let mut autocomplete_scores = Vec::new();
+        for s in input {
+            if let (auto, 0) = syntax_score(s)? {
+                autocomplete_scores.push(auto);
+            }
+        }
+        if autocomplete_scores.is_empty() {
+            return Err("no incomplete lines in input".into());
+        }
+
+        autocomplete_scores.sort_unstable();
+        Ok(autocomplete_scores[autocomplete_scores.len() / 2])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,7 +153,7 @@ mod tests {
 
         let autocomplete_scores = scores
             .into_iter()
-            .filter_map(|(auto, error)| (error == 0).then(|| auto))
+            .filter_map(|(auto, error)| (error == 0).then_some(auto))
             .collect::<Vec<_>>();
         assert_eq!(
             autocomplete_scores,
@@ -115,28 +161,3 @@ mod tests {
         );
     }
 }
-
-fn main() {
-    println!("Enter input sequence: ");
-    let stdin = std::io::stdin();
-    let parsed_inputs = parsing_input::<_, String>(stdin.lock());
-
-    let scores = parsed_inputs
-        .into_iter()
-        .map(|s| syntax_score(&s))
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap();
-
-    let error_score = scores.iter().map(|&(_auto, error)| error).sum::<u64>();
-    println!("error score: {:?}", error_score);
-
-    let autocomplete_scores = scores
-        .into_iter()
-        .filter_map(|(auto, error)| (error == 0).then(|| auto))
-        .collect::<std::collections::BinaryHeap<_>>()
-        .into_sorted_vec();
-    println!(
-        "autocomplete count: {:?}",
-        autocomplete_scores[autocomplete_scores.len() / 2]
-    );
-}
@@ -0,0 +1,162 @@
+use crate::grid::Grid;
+use crate::solver::Solver;
+
+pub fn advance_epoch(mut mat: Grid<u32>) -> Grid<u32> {
+    const FLASH_THRESHOLD: u32 = 10;
+    for cell in mat.iter_mut() {
+        *cell += 1;
+    }
+
+    let mut flash_stack: Vec<_> = mat
+        .cells()
+        .filter(|&(_, &cell)| cell == FLASH_THRESHOLD)
+        .map(|(coord, _)| coord)
+        .collect();
+    while let Some(coord) = flash_stack.pop() {
+        for adj in mat.adj8(coord) {
+            if mat[adj] < FLASH_THRESHOLD {
+                mat[adj] += 1;
+                if mat[adj] == FLASH_THRESHOLD {
+                    flash_stack.push(adj);
+                }
+            }
+        }
+    }
+
+    for cell in mat.iter_mut() {
+        if *cell == FLASH_THRESHOLD {
+            *cell = 0;
+        }
+    }
+
+    mat
+}
+
+pub fn sync_epoch(mut mat: Grid<u32>) -> u32 {
+    fn is_synced(mat: &Grid<u32>) -> bool {
+        mat.iter().all(|&cell| cell == 0)
+    }
+
+    let mut count: u32 = 0;
+    while !is_synced(&mat) {
+        count += 1;
+        mat = advance_epoch(mat);
+    }
+
+    count
+}
+
+pub fn count_flashes(mut mat: Grid<u32>, epochs: usize) -> usize {
+    let mut count = 0;
+    for _ in 0..epochs {
+        mat = advance_epoch(mat);
+        count += mat.iter().filter(|&&cell| cell == 0).count();
+    }
+
+    count
+}
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    type Input = Grid<u32>;
+    type Error = &'static str;
+    type Answer1 = usize;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        Grid::from_digits(input)
+    }
+
+    fn part1(dumbo_grid: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Ok(count_flashes(dumbo_grid.clone(), 100))
+    }
+
+    fn part2(dumbo_grid: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        Ok(sync_epoch(dumbo_grid.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_advance_epoch_small_eg1() {
+        let dumbo_grid = Grid::from([
+            [1, 1, 1, 1, 1],
+            [1, 9, 9, 9, 1],
+            [1, 9, 1, 9, 1],
+            [1, 9, 9, 9, 1],
+            [1, 1, 1, 1, 1],
+        ]);
+        let dumbo_grid_next = advance_epoch(dumbo_grid);
+        assert_eq!(
+            dumbo_grid_next,
+            Grid::from([
+                [3, 4, 5, 4, 3],
+                [4, 0, 0, 0, 4],
+                [5, 0, 0, 0, 5],
+                [4, 0, 0, 0, 4],
+                [3, 4, 5, 4, 3],
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_advance_epoch_small_eg2() {
+        let dumbo_grid = Grid::from([
+            [3, 4, 5, 4, 3],
+            [4, 0, 0, 0, 4],
+            [5, 0, 0, 0, 5],
+            [4, 0, 0, 0, 4],
+            [3, 4, 5, 4, 3],
+        ]);
+        let dumbo_grid_next = advance_epoch(dumbo_grid);
+        assert_eq!(
+            dumbo_grid_next,
+            Grid::from([
+                [4, 5, 6, 5, 4],
+                [5, 1, 1, 1, 5],
+                [6, 1, 1, 1, 6],
+                [5, 1, 1, 1, 5],
+                [4, 5, 6, 5, 4],
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_count_flashes_eg() {
+        let dumbo_grid = Grid::from([
+            [5, 4, 8, 3, 1, 4, 3, 2, 2, 3],
+            [2, 7, 4, 5, 8, 5, 4, 7, 1, 1],
+            [5, 2, 6, 4, 5, 5, 6, 1, 7, 3],
+            [6, 1, 4, 1, 3, 3, 6, 1, 4, 6],
+            [6, 3, 5, 7, 3, 8, 5, 4, 7, 8],
+            [4, 1, 6, 7, 5, 2, 4, 6, 4, 5],
+            [2, 1, 7, 6, 8, 4, 1, 7, 2, 1],
+            [6, 8, 8, 2, 8, 8, 1, 1, 3, 4],
+            [4, 8, 4, 6, 8, 4, 8, 5, 5, 4],
+            [5, 2, 8, 3, 7, 5, 1, 5, 2, 6],
+        ]);
+        assert_eq!(count_flashes(dumbo_grid.clone(), 10), 204);
+        assert_eq!(count_flashes(dumbo_grid, 100), 1656);
+    }
+
+    #[test]
+    fn test_sync_epoch_eg() {
+        let dumbo_grid = Grid::from([
+            [5, 4, 8, 3, 1, 4, 3, 2, 2, 3],
+            [2, 7, 4, 5, 8, 5, 4, 7, 1, 1],
+            [5, 2, 6, 4, 5, 5, 6, 1, 7, 3],
+            [6, 1, 4, 1, 3, 3, 6, 1, 4, 6],
+            [6, 3, 5, 7, 3, 8, 5, 4, 7, 8],
+            [4, 1, 6, 7, 5, 2, 4, 6, 4, 5],
+            [2, 1, 7, 6, 8, 4, 1, 7, 2, 1],
+            [6, 8, 8, 2, 8, 8, 1, 1, 3, 4],
+            [4, 8, 4, 6, 8, 4, 8, 5, 5, 4],
+            [5, 2, 8, 3, 7, 5, 1, 5, 2, 6],
+        ]);
+        assert_eq!(sync_epoch(dumbo_grid), 195);
+    }
+}
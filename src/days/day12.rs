@@ -1,25 +1,22 @@
-use aoc_lib::utils::parsing_input;
+use crate::parse::{edge, finish, word, ParseError};
+use crate::solver::Solver;
+use crate::utils::{parsing_input_strict, LineError};
 use core::str::FromStr;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Edge(String, String);
 
 impl FromStr for Edge {
-    type Err = &'static str;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut iter = s.split('-');
-        let n1 = iter.next().ok_or("no items in string")?;
-        let n2 = iter.next().ok_or("no second item in string")?;
-        iter.next()
-            .is_none()
-            .then(move || Edge(n1.to_string(), n2.to_string()))
-            .ok_or("more than 2 items in string")
+        let (n1, n2) = finish(s, edge(word))?;
+        Ok(Edge(n1.to_string(), n2.to_string()))
     }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-enum CaveType {
+pub enum CaveType {
     Small,
     Big,
     Terminal,
@@ -41,7 +38,7 @@ pub const NODE_END: Node = Node {
 };
 
 impl Node {
-    fn make_builder() -> impl (FnMut(String) -> Self) {
+    fn make_builder() -> impl FnMut(String) -> Self {
         let mut node_id_lookup = std::collections::HashMap::<String, Node>::new();
         node_id_lookup.insert("start".to_string(), NODE_START);
         node_id_lookup.insert("end".to_string(), NODE_END);
@@ -58,7 +55,6 @@ impl Node {
                         CaveType::Small
                     },
                 };
-                println!("node mapping: {:?} == {:?}", s, node.id);
                 node_id_lookup.insert(s, node);
 
                 node
@@ -115,13 +111,13 @@ pub struct PathStreamIter<'a> {
 }
 
 impl<'a> PathStreamIter<'a> {
-    pub fn new(graph: &'a Graph) -> PathStreamIter<'a> {
+    pub fn new(graph: &'a Graph, allow_small_twice: bool) -> PathStreamIter<'a> {
         let mut s = Self {
             graph,
             path: Vec::new(),
             node_exits: Vec::new(),
             visited: std::collections::HashMap::new(),
-            visited_small_twice: false,
+            visited_small_twice: !allow_small_twice,
         };
         s.push_if_valid(NODE_START.id, 0);
 
@@ -190,7 +186,7 @@ impl<'a> PathStreamIter<'a> {
     }
 
     fn head(&self) -> Option<(usize, &usize)> {
-        let path_head = (!self.path.is_empty()).then(|| &self.path[self.path.len() - 1])?;
+        let path_head = self.path.last()?;
         let last_exit = &self.node_exits[self.path.len() - 1];
 
         Some((*path_head, last_exit))
@@ -219,16 +215,10 @@ impl<'a> PathStreamIter<'a> {
             }
         }
     }
-
-    /*
-    fn iter<'b>(&'b mut self) -> impl 'b + Iterator<Item = &'b Vec<Node>> {
-        std::iter::repeat(()).filter_map(move |_| self.next())
-    }
-    */
 }
 
-pub fn count_paths(graph: &Graph) -> usize {
-    let mut path_iterlike = PathStreamIter::new(graph);
+pub fn count_paths(graph: &Graph, allow_small_twice: bool) -> usize {
+    let mut path_iterlike = PathStreamIter::new(graph, allow_small_twice);
 
     let mut result = 0;
     while let Some(_path_ref) = path_iterlike.next_ref() {
@@ -238,6 +228,29 @@ pub fn count_paths(graph: &Graph) -> usize {
     result
 }
 
+pub struct Day12;
+
+impl Solver for Day12 {
+    type Input = Graph;
+    type Error = LineError;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        let edges =
+            parsing_input_strict::<_, Edge>(input.as_bytes()).collect::<Result<Vec<_>, _>>()?;
+        Ok(Graph::new(edges.into_iter()))
+    }
+
+    fn part1(graph: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Ok(count_paths(graph, false))
+    }
+
+    fn part2(graph: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        Ok(count_paths(graph, true))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -266,6 +279,85 @@ mod tests {
                 vec![3],
             ]
         );
+        assert_eq!(
+            graph.cave_types,
+            vec![
+                CaveType::Terminal,
+                CaveType::Terminal,
+                CaveType::Big,
+                CaveType::Small,
+                CaveType::Small,
+                CaveType::Small,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_count_paths_once_eg1() {
+        let cave_edges = vec![
+            Edge("start".to_string(), "A".to_string()),
+            Edge("start".to_string(), "b".to_string()),
+            Edge("A".to_string(), "c".to_string()),
+            Edge("A".to_string(), "b".to_string()),
+            Edge("b".to_string(), "d".to_string()),
+            Edge("A".to_string(), "end".to_string()),
+            Edge("b".to_string(), "end".to_string()),
+        ];
+        let graph = Graph::new(cave_edges.into_iter());
+        println!("graph: {:?}", graph);
+
+        let result = count_paths(&graph, false);
+        assert_eq!(result, 10);
+    }
+
+    #[test]
+    fn test_count_paths_once_eg2() {
+        let cave_edges = vec![
+            Edge("dc".to_string(), "end".to_string()),
+            Edge("HN".to_string(), "start".to_string()),
+            Edge("start".to_string(), "kj".to_string()),
+            Edge("dc".to_string(), "start".to_string()),
+            Edge("dc".to_string(), "HN".to_string()),
+            Edge("LN".to_string(), "dc".to_string()),
+            Edge("HN".to_string(), "end".to_string()),
+            Edge("kj".to_string(), "sa".to_string()),
+            Edge("kj".to_string(), "HN".to_string()),
+            Edge("kj".to_string(), "dc".to_string()),
+        ];
+        let graph = Graph::new(cave_edges.into_iter());
+        println!("graph: {:?}", graph);
+
+        let result = count_paths(&graph, false);
+        assert_eq!(result, 19);
+    }
+
+    #[test]
+    fn test_count_paths_once_eg3() {
+        let cave_edges = vec![
+            Edge("fs".to_string(), "end".to_string()),
+            Edge("he".to_string(), "DX".to_string()),
+            Edge("fs".to_string(), "he".to_string()),
+            Edge("start".to_string(), "DX".to_string()),
+            Edge("pj".to_string(), "DX".to_string()),
+            Edge("end".to_string(), "zg".to_string()),
+            Edge("zg".to_string(), "sl".to_string()),
+            Edge("zg".to_string(), "pj".to_string()),
+            Edge("pj".to_string(), "he".to_string()),
+            Edge("RW".to_string(), "he".to_string()),
+            Edge("fs".to_string(), "DX".to_string()),
+            Edge("pj".to_string(), "RW".to_string()),
+            Edge("zg".to_string(), "RW".to_string()),
+            Edge("start".to_string(), "pj".to_string()),
+            Edge("he".to_string(), "WI".to_string()),
+            Edge("zg".to_string(), "he".to_string()),
+            Edge("pj".to_string(), "fs".to_string()),
+            Edge("start".to_string(), "RW".to_string()),
+        ];
+        let graph = Graph::new(cave_edges.into_iter());
+        println!("graph: {:?}", graph);
+
+        let result = count_paths(&graph, false);
+        assert_eq!(result, 226);
     }
 
     #[test]
@@ -290,7 +382,7 @@ mod tests {
         };
         println!("graph: {:?}\n", graph);
 
-        let mut path_streamiter = PathStreamIter::new(&graph);
+        let mut path_streamiter = PathStreamIter::new(&graph, true);
 
         assert_eq!(
             path_streamiter.next_ref(),
@@ -396,7 +488,7 @@ mod tests {
         let graph = Graph::new(cave_edges.into_iter());
         println!("graph: {:?}", graph);
 
-        let result = count_paths(&graph);
+        let result = count_paths(&graph, true);
         assert_eq!(result, 103);
     }
 
@@ -425,17 +517,7 @@ mod tests {
         let graph = Graph::new(cave_edges.into_iter());
         println!("graph: {:?}", graph);
 
-        let result = count_paths(&graph);
+        let result = count_paths(&graph, true);
         assert_eq!(result, 3509);
     }
 }
-
-fn main() {
-    println!("Enter input:");
-    let stdin = std::io::stdin();
-    let parsed_inputs = parsing_input::<_, Edge>(stdin.lock());
-
-    let graph = Graph::new(parsed_inputs);
-    let result = count_paths(&graph);
-    println!("paths count: {:?}", result);
-}
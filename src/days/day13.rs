@@ -1,57 +1,43 @@
-use aoc_lib::_2d_int::Point;
-use aoc_lib::utils::ArrayWrapper;
-use core::str::FromStr;
-use std::io::BufRead;
+use crate::_2d_int::Point;
+use crate::parse::{blank_line, finish, integer, lines, point, PResult, ParseError};
+use crate::solver::Solver;
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::combinator::map;
+use nom::sequence::{preceded, separated_pair};
 
 use std::collections::{HashMap, HashSet};
 
-fn read_input<R: BufRead>(
-    mut reader: R,
-) -> Result<(Vec<Point<i32>>, Vec<CartesianLine<i32>>), &'static str> {
-    let mut buffer = String::new();
-    reader.read_to_string(&mut buffer).map_err(|_| "bad read")?;
-
-    let strings = buffer.split("\n\n").collect::<ArrayWrapper<&str, 2>>().0;
-
-    let points: Vec<Point<i32>> = strings[0]
-        .trim()
-        .split('\n')
-        .map(Point::from_str)
-        .collect::<Result<_, _>>()
-        .map_err(|_| "bad point")?;
-
-    let folds: Vec<CartesianLine<i32>> = strings[1]
-        .trim()
-        .split('\n')
-        .map(|s| -> Result<CartesianLine<i32>, &'static str> {
-            if !s.starts_with("fold along ") {
-                return Err("bad fold format");
-            }
-            let s = &s[("fold along ".len())..];
-
-            Ok(match &s[..2] {
-                "x=" => CartesianLine::Vertical {
-                    x: i32::from_str(&s[2..]).map_err(|_| "bad fold value")?,
-                },
-                "y=" => CartesianLine::Horizontal {
-                    y: i32::from_str(&s[2..]).map_err(|_| "bad fold value")?,
-                },
-                _ => return Err("bad fold format"),
-            })
-        })
-        .collect::<Result<_, _>>()
-        .map_err(|_| "bad point")?;
+pub type Instructions = (Vec<Point<i32>>, Vec<CartesianLine<i32>>);
+
+pub fn fold_line(input: &str) -> PResult<'_, CartesianLine<i32>> {
+    preceded(
+        tag("fold along "),
+        alt((
+            map(preceded(tag("x="), integer), |x| CartesianLine::Vertical {
+                x,
+            }),
+            map(preceded(tag("y="), integer), |y| {
+                CartesianLine::Horizontal { y }
+            }),
+        )),
+    )(input)
+}
 
-    Ok((points, folds))
+pub fn parse_input(input: &str) -> Result<Instructions, ParseError> {
+    finish(
+        input,
+        separated_pair(lines(point), blank_line, lines(fold_line)),
+    )
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-enum CartesianLine<T> {
+pub enum CartesianLine<T> {
     Horizontal { y: T },
     Vertical { x: T },
 }
 
-fn fold_over(point: Point<i32>, line: CartesianLine<i32>) -> Point<i32> {
+pub fn fold_over(point: Point<i32>, line: CartesianLine<i32>) -> Point<i32> {
     use CartesianLine::*;
 
     match line {
@@ -66,7 +52,10 @@ fn fold_over(point: Point<i32>, line: CartesianLine<i32>) -> Point<i32> {
     }
 }
 
-fn unique_folded(points: &[Point<i32>], fold_lines: &[CartesianLine<i32>]) -> HashSet<Point<i32>> {
+pub fn unique_folded(
+    points: &[Point<i32>],
+    fold_lines: &[CartesianLine<i32>],
+) -> HashSet<Point<i32>> {
     let mut x_vals: HashMap<_, _> = points.iter().map(|p| (p.x, p.x)).collect();
     let mut y_vals: HashMap<_, _> = points.iter().map(|p| (p.y, p.y)).collect();
 
@@ -95,15 +84,14 @@ fn unique_folded(points: &[Point<i32>], fold_lines: &[CartesianLine<i32>]) -> Ha
         .collect()
 }
 
-fn print_dots<'a, C>(dots: C)
+pub fn render_dots<'a, C>(dots: C) -> String
 where
     C: 'a + IntoIterator<Item = &'a Point<i32>>,
 {
     let mut paper = Vec::<Vec<bool>>::new();
 
     for dot in dots.into_iter() {
-        let dot_x = dot.x as usize;
-        let dot_y = dot.y as usize;
+        let (dot_y, dot_x) = dot.to_index().expect("folded dots are non-negative");
 
         if dot_y >= paper.len() {
             paper.resize(dot_y + 1, Default::default());
@@ -115,13 +103,37 @@ where
         paper[dot_y][dot_x] = true;
     }
 
-    for line in paper {
-        println!(
-            "{}",
+    paper
+        .iter()
+        .map(|line| {
             line.iter()
                 .map(|b| if *b { '#' } else { ' ' })
                 .collect::<String>()
-        );
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub struct Day13;
+
+impl Solver for Day13 {
+    type Input = Instructions;
+    type Error = ParseError;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        parse_input(input)
+    }
+
+    fn part1((points, folds): &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        let dots = unique_folded(points, &folds[..1.min(folds.len())]);
+        Ok(dots.len())
+    }
+
+    fn part2((points, folds): &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        let dots = unique_folded(points, folds);
+        Ok(render_dots(&dots))
     }
 }
 
@@ -129,6 +141,24 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_input() {
+        let (points, folds) =
+            parse_input("6,10\n0,14\n\nfold along y=7\nfold along x=5\n").unwrap();
+        assert_eq!(points, vec![Point { x: 6, y: 10 }, Point { x: 0, y: 14 }]);
+        assert_eq!(
+            folds,
+            vec![
+                CartesianLine::Horizontal { y: 7 },
+                CartesianLine::Vertical { x: 5 }
+            ]
+        );
+
+        let err = parse_input("6,10\n0,14\n\nfold along z=7\n").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 12));
+        assert!(parse_input("6,10\n0,14\nfold along y=7\n").is_err());
+    }
+
     #[test]
     fn test_count_visible_points_example() {
         let points = vec![
@@ -159,7 +189,7 @@ mod tests {
 
         let mut calc_result = unique_folded(&points, &fold_lines);
 
-        for p in vec![
+        for p in [
             Point { x: 0, y: 0 },
             Point { x: 0, y: 1 },
             Point { x: 0, y: 2 },
@@ -176,23 +206,9 @@ mod tests {
             Point { x: 3, y: 0 },
             Point { x: 2, y: 0 },
             Point { x: 1, y: 0 },
-        ]
-        .into_iter()
-        {
+        ] {
             assert!(calc_result.remove(&p));
         }
         assert!(calc_result.is_empty());
     }
 }
-
-fn main() {
-    println!("Enter input:");
-    let stdin = std::io::stdin();
-    let (points, folds) = read_input(stdin.lock()).unwrap();
-
-    let dots = unique_folded(&points, &folds);
-    println!("unique folded points: {:?}", dots.len());
-
-    println!("dots:");
-    print_dots(&dots);
-}
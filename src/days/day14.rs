@@ -1,54 +1,51 @@
-use aoc_lib::utils::ArrayWrapper;
+use crate::num::pow_apply;
+use crate::parse::{blank_line, finish, lines, rule, PResult, ParseError};
+use crate::solver::Solver;
 
 use core::str::FromStr;
 use itertools::Itertools;
+use nom::character::complete::{alpha1, anychar};
+use nom::combinator::map;
+use nom::sequence::{pair, separated_pair};
 use std::collections::HashMap;
-use std::io::BufRead;
 
-struct Insertion {
-    first: char,
-    last: char,
-    insert: char,
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Insertion {
+    pub first: char,
+    pub last: char,
+    pub insert: char,
 }
 
 impl FromStr for Insertion {
-    type Err = &'static str;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() != 7 {
-            return Err("wrong string length");
-        }
-        match s.chars().collect::<ArrayWrapper<char, 7>>() {
-            ArrayWrapper([first, last, ' ', '-', '>', ' ', insert]) => Ok(Self {
-                first,
-                last,
-                insert,
-            }),
-            _ => Err("wrong format"),
-        }
+        finish(s, insertion)
     }
 }
 
-fn read_input<R: BufRead>(mut reader: R) -> Result<(String, Vec<Insertion>), &'static str> {
-    let mut buffer = String::new();
-    reader.read_to_string(&mut buffer).map_err(|_| "bad read")?;
-
-    let strings = buffer.split("\n\n").collect::<ArrayWrapper<&str, 2>>().0;
-
-    let initial_seq = strings[0].trim();
-    let insertions: Vec<_> = strings[1]
-        .trim()
-        .split('\n')
-        .map(Insertion::from_str)
-        .collect::<Result<_, _>>()?;
+pub fn insertion(input: &str) -> PResult<'_, Insertion> {
+    map(
+        rule(pair(anychar, anychar), anychar),
+        |((first, last), insert)| Insertion {
+            first,
+            last,
+            insert,
+        },
+    )(input)
+}
 
-    Ok((initial_seq.to_string(), insertions))
+pub fn parse_input(input: &str) -> Result<(String, Vec<Insertion>), ParseError> {
+    finish(
+        input,
+        separated_pair(map(alpha1, String::from), blank_line, lines(insertion)),
+    )
 }
 
-type PairCounts = HashMap<(char, char), usize>;
-type PolymerPairCountMap = HashMap<(char, char), PairCounts>;
+pub type PairCounts = HashMap<(char, char), usize>;
+pub type PolymerPairCountMap = HashMap<(char, char), PairCounts>;
 
-fn new_pair_counts(s: &str) -> PairCounts {
+pub fn new_pair_counts(s: &str) -> PairCounts {
     s.chars()
         .tuple_windows()
         .fold(PairCounts::new(), |mut pair_counts, pair| {
@@ -59,7 +56,7 @@ fn new_pair_counts(s: &str) -> PairCounts {
         })
 }
 
-fn new_map<I: Iterator<Item = Insertion>>(insertions: I) -> PolymerPairCountMap {
+pub fn new_map<I: Iterator<Item = Insertion>>(insertions: I) -> PolymerPairCountMap {
     insertions
         .map(|insert| {
             let mut new = HashMap::new();
@@ -77,14 +74,14 @@ fn new_map<I: Iterator<Item = Insertion>>(insertions: I) -> PolymerPairCountMap
         .collect()
 }
 
-fn next_tier(insertion_map: &PolymerPairCountMap) -> PolymerPairCountMap {
+pub fn next_tier(insertion_map: &PolymerPairCountMap) -> PolymerPairCountMap {
     insertion_map
         .iter()
         .map(|(&k, pair_counts)| (k, apply_tier(pair_counts, insertion_map)))
         .collect()
 }
 
-fn apply_tier(pattern_counts: &PairCounts, insertion_map: &PolymerPairCountMap) -> PairCounts {
+pub fn apply_tier(pattern_counts: &PairCounts, insertion_map: &PolymerPairCountMap) -> PairCounts {
     let mut result = PairCounts::new();
 
     for (pair, &pair_count) in pattern_counts.iter() {
@@ -102,29 +99,21 @@ fn apply_tier(pattern_counts: &PairCounts, insertion_map: &PolymerPairCountMap)
     result
 }
 
-fn polymerized_counts(
+pub fn polymerized_counts(
     template: &str,
-    mut insertion_map: PolymerPairCountMap,
+    insertion_map: PolymerPairCountMap,
     iterations: u32,
 ) -> PairCounts {
-    let bit_len = 32 - iterations.leading_zeros();
-
-    let mut template = new_pair_counts(template);
-
-    if iterations > 0 {
-        for i in 0..(bit_len - 1) {
-            if (iterations & (1 << i)) != 0 {
-                template = apply_tier(&template, &insertion_map);
-            }
-            insertion_map = next_tier(&insertion_map);
-        }
-        template = apply_tier(&template, &insertion_map);
-    }
-
-    template
+    pow_apply(
+        new_pair_counts(template),
+        insertion_map,
+        iterations.into(),
+        |template, insertion_map| apply_tier(&template, insertion_map),
+        next_tier,
+    )
 }
 
-fn element_counts(pair_counts: &PairCounts) -> HashMap<char, usize> {
+pub fn element_counts(pair_counts: &PairCounts) -> HashMap<char, usize> {
     let mut result = pair_counts
         .iter()
         .fold(HashMap::new(), |mut map, (pair, &count)| {
@@ -142,10 +131,56 @@ fn element_counts(pair_counts: &PairCounts) -> HashMap<char, usize> {
     result
 }
 
+pub fn polymer_score(template: &str, insertions: &[Insertion], iterations: u32) -> usize {
+    let polymer = polymerized_counts(template, new_map(insertions.iter().copied()), iterations);
+    let elems = element_counts(&polymer);
+
+    elems.values().max().copied().unwrap_or_default()
+        - elems.values().min().copied().unwrap_or_default()
+}
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    type Input = (String, Vec<Insertion>);
+    type Error = ParseError;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        parse_input(input)
+    }
+
+    fn part1((template, insertions): &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Ok(polymer_score(template, insertions, 10))
+    }
+
+    fn part2((template, insertions): &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        Ok(polymer_score(template, insertions, 40))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_input() {
+        let (template, insertions) = parse_input("NNCB\n\nCH -> B\nHH -> N\n").unwrap();
+        assert_eq!(template, "NNCB");
+        assert_eq!(
+            insertions,
+            vec![
+                Insertion::from_str("CH -> B").unwrap(),
+                Insertion::from_str("HH -> N").unwrap()
+            ]
+        );
+
+        let err = parse_input("NNCB\n\nCH -> B\nHH => N\n").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 3));
+        assert!(parse_input("NNCB\nCH -> B\n").is_err());
+    }
+
     #[test]
     fn test_polymerized_eg() {
         let template = "NNCB";
@@ -279,17 +314,3 @@ mod tests {
         assert_eq!(result, 2188189693529)
     }
 }
-
-fn main() {
-    println!("Enter input sequence: ");
-    let stdin = std::io::stdin();
-    let (template, insertions) = read_input(stdin.lock()).unwrap();
-
-    let polymer = polymerized_counts(&template, new_map(insertions.into_iter()), 40);
-    let elems = element_counts(&polymer);
-    println!("polymer element counts: {:?}", elems);
-    println!(
-        "result = {:?}",
-        elems.values().cloned().max().unwrap() - elems.values().cloned().min().unwrap()
-    );
-}
@@ -0,0 +1,228 @@
+use crate::grid::{Grid, TiledGrid};
+use crate::search::{dijkstra, Path};
+use crate::solver::Solver;
+
+// Risk levels are in `1..=9`; a risk of 0 would underflow.
+pub fn increment_wrap(&risk: &u32, (tile_row, tile_col): (usize, usize)) -> u32 {
+    (risk + (tile_row + tile_col) as u32 - 1) % 9 + 1
+}
+
+pub fn best_path<F>(cave: &TiledGrid<u32, F>) -> Path<(usize, usize), u32>
+where
+    F: Fn(&u32, (usize, usize)) -> u32,
+{
+    assert!(!cave.is_empty(), "zero-sized grid");
+    let end = (cave.height() - 1, cave.width() - 1);
+
+    dijkstra(
+        (0, 0),
+        |&coord| {
+            cave.adj4(coord)
+                .map(move |c| (c, cave.get(c).expect("adjacent coords are in bounds")))
+        },
+        |&coord| coord == end,
+    )
+    .expect("grid is connected")
+}
+
+pub fn path_risk(grid: &Grid<u32>) -> u32 {
+    path_risk_wrapped(grid, (1, 1))
+}
+
+// `tiles` counts the copies of `grid` down and across.
+pub fn path_risk_wrapped(grid: &Grid<u32>, tiles: (usize, usize)) -> u32 {
+    best_path(&TiledGrid::new(grid, tiles, increment_wrap)).cost
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Highlight {
+    Ansi,
+    Marker(char),
+}
+
+pub fn render_path<F>(
+    cave: &TiledGrid<u32, F>,
+    path: &[(usize, usize)],
+    highlight: Highlight,
+) -> String
+where
+    F: Fn(&u32, (usize, usize)) -> u32,
+{
+    let mut on_path = Grid::new(cave.height(), cave.width(), false);
+    for &coord in path {
+        on_path[coord] = true;
+    }
+
+    let mut result = String::new();
+    for row in 0..on_path.height() {
+        for col in 0..on_path.width() {
+            let risk = cave.get((row, col)).expect("coords are in bounds");
+            match (on_path[(row, col)], highlight) {
+                (false, _) => result.push_str(&risk.to_string()),
+                (true, Highlight::Ansi) => result.push_str(&format!("\x1b[1;31m{}\x1b[0m", risk)),
+                (true, Highlight::Marker(c)) => result.push(c),
+            }
+        }
+        result.push('\n');
+    }
+    result
+}
+
+// The map with the best path drawn on it, then the path's risk and how many
+// positions the search expanded.
+pub fn path_report(grid: &Grid<u32>, tiles: (usize, usize), highlight: Highlight) -> String {
+    let cave = TiledGrid::new(grid, tiles, increment_wrap);
+    let path = best_path(&cave);
+    format!(
+        "{}risk {}, {} positions expanded",
+        render_path(&cave, &path.nodes, highlight),
+        path.cost,
+        path.expanded
+    )
+}
+
+pub struct Day15;
+
+impl Solver for Day15 {
+    type Input = Grid<u32>;
+    type Error = &'static str;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        let grid = Grid::from_digits(input)?;
+        if grid.iter().any(|&risk| risk == 0) {
+            return Err("risk levels must be 1 to 9");
+        }
+        Ok(grid)
+    }
+
+    fn part1(cave_map: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Ok(path_risk(cave_map))
+    }
+
+    fn part2(cave_map: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        Ok(path_risk_wrapped(cave_map, (5, 5)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chiton_path_risk_eg() {
+        let cave_map = Grid::from([
+            [1, 1, 6, 3, 7, 5, 1, 7, 4, 2],
+            [1, 3, 8, 1, 3, 7, 3, 6, 7, 2],
+            [2, 1, 3, 6, 5, 1, 1, 3, 2, 8],
+            [3, 6, 9, 4, 9, 3, 1, 5, 6, 9],
+            [7, 4, 6, 3, 4, 1, 7, 1, 1, 1],
+            [1, 3, 1, 9, 1, 2, 8, 1, 3, 7],
+            [1, 3, 5, 9, 9, 1, 2, 4, 2, 1],
+            [3, 1, 2, 5, 4, 2, 1, 6, 3, 9],
+            [1, 2, 9, 3, 1, 3, 8, 5, 2, 1],
+            [2, 3, 1, 1, 9, 4, 4, 5, 8, 1],
+        ]);
+
+        let risk = path_risk(&cave_map);
+        assert_eq!(risk, 40);
+    }
+
+    #[test]
+    fn test_chiton_path_risk_wrapped_eg() {
+        let cave_map = Grid::from([
+            [1, 1, 6, 3, 7, 5, 1, 7, 4, 2],
+            [1, 3, 8, 1, 3, 7, 3, 6, 7, 2],
+            [2, 1, 3, 6, 5, 1, 1, 3, 2, 8],
+            [3, 6, 9, 4, 9, 3, 1, 5, 6, 9],
+            [7, 4, 6, 3, 4, 1, 7, 1, 1, 1],
+            [1, 3, 1, 9, 1, 2, 8, 1, 3, 7],
+            [1, 3, 5, 9, 9, 1, 2, 4, 2, 1],
+            [3, 1, 2, 5, 4, 2, 1, 6, 3, 9],
+            [1, 2, 9, 3, 1, 3, 8, 5, 2, 1],
+            [2, 3, 1, 1, 9, 4, 4, 5, 8, 1],
+        ]);
+
+        let risk = path_risk_wrapped(&cave_map, (5, 5));
+        assert_eq!(risk, 315);
+    }
+
+    #[test]
+    fn test_best_path_eg() {
+        let cave_map = Grid::from([[1, 1, 6], [1, 3, 8], [2, 1, 3]]);
+        let cave = TiledGrid::new(&cave_map, (1, 1), increment_wrap);
+
+        let path = best_path(&cave);
+        assert_eq!(path.cost, 7);
+        assert_eq!(path.nodes, vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)]);
+        assert!(path.expanded <= 9);
+        assert_eq!(
+            render_path(&cave, &path.nodes, Highlight::Marker('#')),
+            "#16\n#38\n###\n"
+        );
+        assert_eq!(
+            render_path(&cave, &path.nodes[..1], Highlight::Ansi),
+            "\x1b[1;31m1\x1b[0m16\n138\n213\n"
+        );
+
+        let cave = TiledGrid::new(&cave_map, (3, 7), increment_wrap);
+        let path = best_path(&cave);
+        assert_eq!(path.nodes.first(), Some(&(0, 0)));
+        assert_eq!(path.nodes.last(), Some(&(8, 20)));
+        assert_eq!(
+            path.nodes[1..]
+                .iter()
+                .map(|&c| cave.get(c).unwrap())
+                .sum::<u32>(),
+            path.cost
+        );
+    }
+
+    #[test]
+    fn test_path_report() {
+        let cave_map = Grid::from([[1, 1, 6], [1, 3, 8], [2, 1, 3]]);
+        let report = path_report(&cave_map, (1, 2), Highlight::Marker('#'));
+        let (map, summary) = report.rsplit_once('\n').unwrap();
+        assert_eq!(map, "#16227\n#38249\n######");
+        assert!(summary.starts_with("risk 16, "));
+        assert_eq!(path_risk_wrapped(&cave_map, (1, 2)), 16);
+        assert_eq!(path_risk_wrapped(&cave_map, (2, 1)), 16);
+    }
+
+    #[test]
+    fn test_increment_wrap() {
+        assert_eq!(increment_wrap(&8, (0, 0)), 8);
+        assert_eq!(increment_wrap(&8, (1, 0)), 9);
+        assert_eq!(increment_wrap(&8, (1, 1)), 1);
+        assert_eq!(increment_wrap(&9, (4, 4)), 8);
+    }
+
+    #[test]
+    fn test_solver_eg() {
+        let input = Day15::parse(
+            "1163751742\n\
+             1381373672\n\
+             2136511328\n\
+             3694931569\n\
+             7463417111\n\
+             1319128137\n\
+             1359912421\n\
+             3125421639\n\
+             1293138521\n\
+             2311944581\n",
+        )
+        .unwrap();
+        assert_eq!(Day15::part1(&input), Ok(40));
+        assert_eq!(Day15::part2(&input), Ok(315));
+    }
+
+    #[test]
+    fn test_parse_rejects_zero_risk() {
+        assert!(Day15::parse("19\n91\n").is_ok());
+        assert_eq!(
+            Day15::parse("19\n01\n").err(),
+            Some("risk levels must be 1 to 9")
+        );
+    }
+}
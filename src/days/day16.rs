@@ -0,0 +1,38 @@
+use crate::bits::{BitsError, HexBits, PacketTree};
+use crate::solver::Solver;
+use core::str::FromStr;
+
+pub struct Day16;
+
+impl Solver for Day16 {
+    type Input = PacketTree;
+    type Error = BitsError;
+    type Answer1 = usize;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        PacketTree::decode(&HexBits::from_str(input.trim())?)
+    }
+
+    fn part1(tree: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Ok(tree.version_sum())
+    }
+
+    fn part2(tree: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        tree.evaluate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solver_egs() {
+        let input = Day16::parse("8A004A801A8002F478\n").unwrap();
+        assert_eq!(Day16::part1(&input), Ok(16));
+
+        let input = Day16::parse("9C0141080250320F1802104A08\n").unwrap();
+        assert_eq!(Day16::part2(&input), Ok(1));
+    }
+}
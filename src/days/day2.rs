@@ -0,0 +1,152 @@
+use crate::parse::{finish, integer, PResult, ParseError};
+use crate::solver::Solver;
+use crate::utils::{parsing_input_strict, LineError};
+
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::char;
+use nom::combinator::value;
+use nom::error::context;
+use nom::sequence::separated_pair;
+use std::str::FromStr;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Direction {
+    Forward,
+    Upward,
+    Downward,
+}
+
+impl FromStr for Direction {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        finish(s, direction)
+    }
+}
+
+pub fn direction(input: &str) -> PResult<'_, Direction> {
+    context(
+        "`forward`, `up` or `down`",
+        alt((
+            value(Direction::Forward, tag("forward")),
+            value(Direction::Upward, tag("up")),
+            value(Direction::Downward, tag("down")),
+        )),
+    )(input)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Command(pub Direction, pub i32);
+
+impl FromStr for Command {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        finish(s, separated_pair(direction, char(' '), integer)).map(|(d, n)| Self(d, n))
+    }
+}
+
+pub fn track_loc<I>(seq: I) -> (i32, i32)
+where
+    I: Iterator<Item = (Direction, i32)>,
+{
+    seq.fold(
+        (0, 0),
+        |(down_up, fwd_bwd), (direction, dist)| match direction {
+            Direction::Forward => (down_up, fwd_bwd + dist),
+            Direction::Upward => (down_up - dist, fwd_bwd),
+            Direction::Downward => (down_up + dist, fwd_bwd),
+        },
+    )
+}
+
+pub fn track_aimed_loc<I>(seq: I) -> (i32, i32, i32)
+where
+    I: Iterator<Item = (Direction, i32)>,
+{
+    seq.fold(
+        (0, 0, 0),
+        |(down_up, fwd_bwd, aim), (direction, dist)| match direction {
+            Direction::Downward => (down_up, fwd_bwd, aim + dist),
+            Direction::Upward => (down_up, fwd_bwd, aim - dist),
+            Direction::Forward => (down_up + dist * aim, fwd_bwd + dist, aim),
+        },
+    )
+}
+
+pub struct Day2;
+
+impl Solver for Day2 {
+    type Input = Vec<(Direction, i32)>;
+    type Error = LineError;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        parsing_input_strict(input.as_bytes())
+            .map(|command| command.map(|Command(d, n)| (d, n)))
+            .collect()
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        let (depth, dist) = track_loc(input.iter().copied());
+        Ok(depth * dist)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        let (depth, dist, _aim) = track_aimed_loc(input.iter().copied());
+        Ok(depth * dist)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_track_loc_example() {
+        let sequence = vec![
+            (Direction::Forward, 5),
+            (Direction::Downward, 5),
+            (Direction::Forward, 8),
+            (Direction::Upward, 3),
+            (Direction::Downward, 8),
+            (Direction::Forward, 2),
+        ];
+        assert_eq!(track_loc(sequence.into_iter()), (10, 15));
+    }
+
+    #[test]
+    fn test_track_aimed_loc_example() {
+        let sequence = vec![
+            (Direction::Forward, 5),
+            (Direction::Downward, 5),
+            (Direction::Forward, 8),
+            (Direction::Upward, 3),
+            (Direction::Downward, 8),
+            (Direction::Forward, 2),
+        ];
+        assert_eq!(track_aimed_loc(sequence.into_iter()), (60, 15, 10));
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_lines() {
+        let input = Day2::parse("forward 5\ndown 5\n").unwrap();
+        assert_eq!(
+            input,
+            vec![(Direction::Forward, 5), (Direction::Downward, 5)]
+        );
+
+        let err = Day2::parse("forward 5\nsideways 3\nup 1\n").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: expected `forward`, `up` or `down`"
+        );
+
+        let err = Day2::parse("down x\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 6: expected integer");
+        assert_eq!(Day2::parse("up 1\nforward\n").unwrap_err().line, 2);
+        assert_eq!(Day2::parse("up 1 2\n").unwrap_err().line, 1);
+    }
+}
@@ -0,0 +1,275 @@
+use crate::bitvector::{BitStorage, BitVector};
+use crate::parse::ParseError;
+use crate::solver::Solver;
+use crate::utils::{parsing_input_strict, AnyError, LineError};
+
+use core::fmt;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TieBreak {
+    One,
+    Zero,
+}
+
+impl TieBreak {
+    pub fn bit(self) -> bool {
+        self == TieBreak::One
+    }
+}
+
+// Which bit wins a column that is evenly split between ones and zeros. The
+// epsilon rate is always the complement of gamma, so it has no policy of its own.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TiePolicy {
+    pub gamma: TieBreak,
+    pub oxygen: TieBreak,
+    pub co2: TieBreak,
+}
+
+impl Default for TiePolicy {
+    fn default() -> Self {
+        Self {
+            gamma: TieBreak::Zero,
+            oxygen: TieBreak::One,
+            co2: TieBreak::Zero,
+        }
+    }
+}
+
+pub fn parse_report<S: BitStorage>(input: &str) -> Result<Vec<BitVector<S>>, LineError> {
+    let mut report: Vec<BitVector<S>> = Vec::new();
+    for (i, result) in parsing_input_strict(input.as_bytes()).enumerate() {
+        let bits: BitVector<S> = result?;
+        if bits.is_empty() {
+            return Err(LineError {
+                line: i + 1,
+                content: String::new(),
+                source: ParseError::at("", "", "binary digits").into(),
+            });
+        }
+        if let Some(width) = report.first().map(BitVector::len) {
+            if bits.len() != width {
+                let content = bits.to_string();
+                let error = match content.char_indices().nth(width) {
+                    Some((j, _)) => ParseError::at(&content, &content[j..], "end of line"),
+                    None => ParseError::at(&content, "", format!("{} bits", width)),
+                };
+                return Err(LineError {
+                    line: i + 1,
+                    content,
+                    source: error.into(),
+                });
+            }
+        }
+        report.push(bits);
+    }
+
+    Ok(report)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ReportError {
+    Empty,
+    TooWide,
+    NotUnique { remaining: usize },
+}
+
+impl fmt::Display for ReportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty report"),
+            Self::TooWide => write!(f, "value does not fit in 128 bits"),
+            Self::NotUnique { remaining } => write!(
+                f,
+                "did not reduce to single remaining value, {} values remaining",
+                remaining
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReportError {}
+
+pub fn calculate_gamma_epsilon<S: BitStorage>(
+    report: &[BitVector<S>],
+    tie: TieBreak,
+) -> Result<(u128, u128), ReportError> {
+    let gamma = BitVector::majority(report, tie.bit()).ok_or(ReportError::Empty)?;
+    let epsilon = !gamma;
+
+    match (gamma.to_uint(), epsilon.to_uint()) {
+        (Some(gamma), Some(epsilon)) => Ok((gamma, epsilon)),
+        _ => Err(ReportError::TooWide),
+    }
+}
+
+pub fn calculate_gas<S: BitStorage>(
+    report: &[BitVector<S>],
+    most_common: bool,
+    tie: TieBreak,
+) -> Result<u128, ReportError> {
+    let mut candidates = report.to_vec();
+    let width = report.first().ok_or(ReportError::Empty)?.len();
+    let mut prefix = BitVector::zeros(width).expect("width already fits the storage");
+
+    for i in 0..width {
+        let target = if most_common {
+            BitVector::majority(&candidates, tie.bit())
+        } else {
+            BitVector::minority(&candidates, tie.bit())
+        };
+        let target = match target {
+            Some(target) => target,
+            None => break,
+        };
+        prefix.set(i, target.get(i));
+        candidates = BitVector::filter_prefix(report, &prefix, i + 1)
+            .copied()
+            .collect();
+
+        if candidates.len() == 1 {
+            break;
+        }
+    }
+
+    if candidates.len() != 1 {
+        return Err(ReportError::NotUnique {
+            remaining: candidates.len(),
+        });
+    }
+
+    candidates[0].to_uint().ok_or(ReportError::TooWide)
+}
+
+pub struct Day3;
+
+impl Solver for Day3 {
+    type Input = Vec<BitVector<u128>>;
+    type Error = AnyError;
+    type Answer1 = u128;
+    type Answer2 = u128;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        Ok(parse_report(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        let (gamma, epsilon) = calculate_gamma_epsilon(input, TiePolicy::default().gamma)?;
+        Ok(gamma
+            .checked_mul(epsilon)
+            .ok_or("power consumption overflowed")?)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        let policy = TiePolicy::default();
+        let oxygen = calculate_gas(input, true, policy.oxygen)?;
+        let co2 = calculate_gas(input, false, policy.co2)?;
+        Ok(oxygen
+            .checked_mul(co2)
+            .ok_or("life support rating overflowed")?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010
+";
+
+    #[test]
+    fn test_gamma_example() {
+        let report = parse_report::<u64>(EXAMPLE).unwrap();
+        assert_eq!(
+            calculate_gamma_epsilon(&report, TieBreak::Zero),
+            Ok((0b10110, 0b1001))
+        );
+
+        // the last column is split 4/4 across the first eight lines
+        assert_eq!(
+            calculate_gamma_epsilon(&report[..8], TieBreak::Zero),
+            Ok((0b10110, 0b01001))
+        );
+        assert_eq!(
+            calculate_gamma_epsilon(&report[..8], TieBreak::One),
+            Ok((0b10111, 0b01000))
+        );
+    }
+
+    #[test]
+    fn test_gas_example() {
+        let report = parse_report::<u64>(EXAMPLE).unwrap();
+        assert_eq!(calculate_gas(&report, true, TieBreak::One), Ok(23));
+        assert_eq!(calculate_gas(&report, false, TieBreak::Zero), Ok(10));
+        assert_eq!(calculate_gas(&report, true, TieBreak::Zero), Ok(22));
+    }
+
+    #[test]
+    fn test_runtime_width() {
+        let wide = format!(
+            "1{}\n0{}\n1{}\n",
+            "0".repeat(69),
+            "1".repeat(69),
+            "1".repeat(69)
+        );
+        let report = parse_report::<u128>(&wide).unwrap();
+        assert_eq!(report[0].len(), 70);
+        assert_eq!(
+            calculate_gamma_epsilon(&report, TieBreak::Zero),
+            Ok(((1 << 69) | ((1 << 69) - 1), 0))
+        );
+        assert_eq!(
+            calculate_gas(&report, false, TieBreak::Zero),
+            Ok((1 << 69) - 1)
+        );
+    }
+
+    #[test]
+    fn test_inconsistent_width() {
+        let err = parse_report::<u64>("10110\n1011\n").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(
+            format!("{:#}", err),
+            "line 2, column 5: expected 5 bits\n  |\n2 | 1011\n  |     ^"
+        );
+
+        let err = parse_report::<u64>("101\n101\n1010\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 3, column 4: expected end of line");
+        assert!(parse_report::<u64>("101\n1x1\n").is_err());
+    }
+
+    #[test]
+    fn test_report_errors() {
+        let err = parse_report::<u64>("\n101\n").unwrap_err();
+        assert_eq!(err.line, 1);
+        assert_eq!(err.to_string(), "line 1, column 1: expected binary digits");
+        assert_eq!(parse_report::<u64>("101\n\n101\n").unwrap_err().line, 2);
+
+        let empty: [BitVector<u64>; 0] = [];
+        assert_eq!(
+            calculate_gamma_epsilon(&empty, TieBreak::Zero),
+            Err(ReportError::Empty)
+        );
+        assert_eq!(
+            calculate_gas(&empty, true, TieBreak::One),
+            Err(ReportError::Empty)
+        );
+
+        let report = parse_report::<u64>("101\n101\n").unwrap();
+        assert_eq!(
+            calculate_gas(&report, true, TieBreak::One),
+            Err(ReportError::NotUnique { remaining: 2 })
+        );
+    }
+}
@@ -1,46 +1,24 @@
+use crate::parse::{blank_line, comma_separated, finish, integer, lines, sections, ParseError};
+use crate::solver::Solver;
+use crate::utils::AnyError;
+
 use core::str::FromStr;
+use nom::character::complete::{space0, space1};
+use nom::multi::separated_list1;
+use nom::sequence::{preceded, separated_pair};
 use std::collections::{HashMap, HashSet};
-use std::error::Error;
-use std::io::BufRead;
-
-type Matrix<T> = Vec<Vec<T>>;
-type AnyError = Box<dyn Error>;
 
-fn read_input<R: BufRead, T>(reader: R) -> Result<(Vec<T>, Vec<Matrix<T>>), AnyError>
-where
-    T: std::str::FromStr,
-    <T as std::str::FromStr>::Err: 'static + Error,
-{
-    let mut lines = reader.lines().peekable();
-
-    let result1 = lines
-        .next()
-        .ok_or("no lines in input")??
-        .split(',')
-        .map(T::from_str)
-        .collect::<Result<_, _>>()?;
-
-    let mut result2 = Vec::new();
-    while lines.next().is_some() && lines.peek().is_some() {
-        result2.push(
-            (&mut lines)
-                .take(5)
-                .map(|s| {
-                    s.map_err(AnyError::from)?
-                        .trim()
-                        .split_whitespace()
-                        .map(T::from_str)
-                        .map(|r| r.map_err(AnyError::from))
-                        .collect::<Result<_, _>>()
-                })
-                .collect::<Result<_, _>>()?,
-        );
-    }
+pub type Matrix<T> = Vec<Vec<T>>;
 
-    Ok((result1, result2))
+pub fn parse_input<T: FromStr>(input: &str) -> Result<(Vec<T>, Vec<Matrix<T>>), ParseError> {
+    let row = preceded(space0, separated_list1(space1, integer));
+    finish(
+        input,
+        separated_pair(comma_separated(integer), blank_line, sections(lines(row))),
+    )
 }
 
-fn first_bingo_winner_score<ITEMS, BOARDS, T, U, const ROW: usize, const COL: usize>(
+pub fn first_bingo_winner_score<ITEMS, BOARDS, T, U, const ROW: usize, const COL: usize>(
     items: ITEMS,
     boards: BOARDS,
 ) -> Option<U>
@@ -96,7 +74,7 @@ where
     None
 }
 
-fn last_bingo_winner_score<ITEMS, BOARDS, T, U, const ROW: usize, const COL: usize>(
+pub fn last_bingo_winner_score<ITEMS, BOARDS, T, U, const ROW: usize, const COL: usize>(
     items: ITEMS,
     boards: BOARDS,
 ) -> Option<U>
@@ -156,13 +134,66 @@ where
     result
 }
 
-fn main() {
-    println!("Enter input: ");
-    let stdin = std::io::stdin();
+pub struct Day4;
+
+impl Solver for Day4 {
+    type Input = (Vec<usize>, Vec<Matrix<usize>>);
+    type Error = AnyError;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        parse_input(input).map_err(AnyError::from)
+    }
+
+    fn part1((items, boards): &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        first_bingo_winner_score::<_, _, _, _, 5, 5>(items.iter().copied(), boards.iter().cloned())
+            .ok_or_else(|| "no board won".into())
+    }
+
+    fn part2((items, boards): &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        last_bingo_winner_score::<_, _, _, _, 5, 5>(items.iter().copied(), boards.iter().cloned())
+            .ok_or_else(|| "no board won".into())
+    }
+}
 
-    let (items, boards) = read_input::<_, usize>(stdin.lock()).unwrap();
-    //println!("items: {:?}\nboards: {:?}", items, boards);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7
+";
+
+    #[test]
+    fn test_bingo_winner_scores_example() {
+        let (items, boards) = parse_input::<usize>(EXAMPLE).unwrap();
+
+        let first = first_bingo_winner_score::<_, _, _, _, 5, 5>(
+            items.iter().copied(),
+            boards.iter().cloned(),
+        );
+        assert_eq!(first, Some(4512));
 
-    let score = last_bingo_winner_score::<_, _, _, _, 5, 5>(items.into_iter(), boards.into_iter());
-    println!("score: {:?}", score);
+        let last =
+            last_bingo_winner_score::<_, _, _, _, 5, 5>(items.into_iter(), boards.into_iter());
+        assert_eq!(last, Some(1924));
+    }
 }
@@ -0,0 +1,361 @@
+use crate::_2d_int::{LineSegment, Point, SegmentKind};
+use crate::solver::Solver;
+use crate::utils::{parsing_input_strict, AnyError};
+
+use std::collections::{BTreeMap, HashMap};
+
+pub fn is_axis_aligned(line_seg: &LineSegment<i32>) -> bool {
+    line_seg.is_horiz() || line_seg.is_vert()
+}
+
+// The directions a vent line can run in. Each family is a set of parallel lines
+// `normal · p = c`, and points along a line are parameterised by `x` (by `y` for
+// vertical lines).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum Family {
+    Horizontal,
+    Vertical,
+    Rising,
+    Falling,
+}
+
+impl Family {
+    const ALL: [Family; 4] = [
+        Family::Horizontal,
+        Family::Vertical,
+        Family::Rising,
+        Family::Falling,
+    ];
+
+    fn normal(self) -> (i64, i64) {
+        match self {
+            Family::Horizontal => (0, 1),
+            Family::Vertical => (1, 0),
+            Family::Rising => (-1, 1),
+            Family::Falling => (1, 1),
+        }
+    }
+
+    fn line_of(self, p: Point<i64>) -> i64 {
+        let (nx, ny) = self.normal();
+        nx * p.x + ny * p.y
+    }
+
+    fn point(self, line: i64, t: i64) -> Point<i64> {
+        match self {
+            Family::Horizontal => Point { x: t, y: line },
+            Family::Vertical => Point { x: line, y: t },
+            Family::Rising => Point { x: t, y: line + t },
+            Family::Falling => Point { x: t, y: line - t },
+        }
+    }
+
+    // The family, line and parameter range of a segment; `None` unless the
+    // segment is axis-aligned or diagonal at 45°. Single points count as
+    // horizontal.
+    fn classify(seg: &LineSegment<i32>) -> Option<(Family, i64, i64, i64)> {
+        let p0 = Point::<i64>::from((seg.p0.x.into(), seg.p0.y.into()));
+        let p1 = Point::<i64>::from((seg.p1.x.into(), seg.p1.y.into()));
+        let d = p1 - p0;
+
+        let family = match seg.kind() {
+            SegmentKind::Degenerate | SegmentKind::Horizontal => Family::Horizontal,
+            SegmentKind::Vertical => Family::Vertical,
+            SegmentKind::Diagonal45 if d.x == d.y => Family::Rising,
+            SegmentKind::Diagonal45 => Family::Falling,
+            SegmentKind::General => return None,
+        };
+        let (t0, t1) = match family {
+            Family::Vertical => (p0.y, p1.y),
+            _ => (p0.x, p1.x),
+        };
+        Some((family, family.line_of(p0), t0.min(t1), t0.max(t1)))
+    }
+}
+
+// A maximal run `lo..=hi` of one line covered by exactly `count` segments.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Piece {
+    family: Family,
+    line: i64,
+    lo: i64,
+    hi: i64,
+    count: usize,
+}
+
+impl Piece {
+    fn ends(&self) -> (Point<i64>, Point<i64>) {
+        (
+            self.family.point(self.line, self.lo),
+            self.family.point(self.line, self.hi),
+        )
+    }
+
+    // The range of `family`'s lines that this piece passes through.
+    fn span(&self, family: Family) -> (i64, i64) {
+        let (p0, p1) = self.ends();
+        let (l0, l1) = (family.line_of(p0), family.line_of(p1));
+        (l0.min(l1), l0.max(l1))
+    }
+}
+
+fn coverage(segments: &[LineSegment<i32>]) -> Option<Vec<Piece>> {
+    let mut events = HashMap::<(Family, i64), Vec<(i64, isize)>>::new();
+    for seg in segments {
+        let (family, line, lo, hi) = Family::classify(seg)?;
+        let line_events = events.entry((family, line)).or_default();
+        line_events.push((lo, 1));
+        line_events.push((hi + 1, -1));
+    }
+
+    let mut pieces = Vec::new();
+    for ((family, line), mut line_events) in events {
+        line_events.sort_unstable();
+        let mut count = 0;
+        for pair in line_events.windows(2) {
+            let ((start, delta), (end, _)) = (pair[0], pair[1]);
+            count += delta;
+            if count > 0 && start < end {
+                pieces.push(Piece {
+                    family,
+                    line,
+                    lo: start,
+                    hi: end - 1,
+                    count: count as usize,
+                });
+            }
+        }
+    }
+    Some(pieces)
+}
+
+// The lattice point where lines of two different families meet, if any.
+fn meet(a: Family, line_a: i64, b: Family, line_b: i64) -> Option<Point<i64>> {
+    let ((ax, ay), (bx, by)) = (a.normal(), b.normal());
+    let det = ax * by - ay * bx;
+    let x = line_a * by - ay * line_b;
+    let y = ax * line_b - line_a * bx;
+    (x % det == 0 && y % det == 0).then(|| Point {
+        x: x / det,
+        y: y / det,
+    })
+}
+
+// Calls `found` for every lattice point shared by a piece in `a` and a piece in
+// `b`, both lists holding pieces of a single family each. Sweeps across the
+// lines of `a`, keeping the pieces of `b` that span the current line ordered by
+// their own line.
+fn crossings<F>(a: &[Piece], b: &[Piece], mut found: F)
+where
+    F: FnMut(Point<i64>, &Piece, &Piece),
+{
+    let (fa, fb) = match (a.first(), b.first()) {
+        (Some(pa), Some(pb)) => (pa.family, pb.family),
+        _ => return,
+    };
+
+    // (position, kind, index) with kind 0 = insert, 1 = query, 2 = remove.
+    let mut events = Vec::with_capacity(a.len() + 2 * b.len());
+    for (i, piece) in b.iter().enumerate() {
+        let (lo, hi) = piece.span(fa);
+        events.push((lo, 0, i));
+        events.push((hi, 2, i));
+    }
+    events.extend(a.iter().enumerate().map(|(i, piece)| (piece.line, 1, i)));
+    events.sort_unstable();
+
+    let mut active = BTreeMap::<i64, Vec<usize>>::new();
+    for (_, kind, i) in events {
+        match kind {
+            0 => active.entry(b[i].line).or_default().push(i),
+            2 => {
+                let on_line = active.get_mut(&b[i].line).expect("inserted earlier");
+                on_line.retain(|&j| j != i);
+                if on_line.is_empty() {
+                    active.remove(&b[i].line);
+                }
+            }
+            _ => {
+                let (lo, hi) = a[i].span(fb);
+                for j in active.range(lo..=hi).flat_map(|(_, on_line)| on_line) {
+                    if let Some(p) = meet(fa, a[i].line, fb, b[*j].line) {
+                        found(p, &a[i], &b[*j]);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// The number of lattice points covered by at least `k` segments, or `None` if
+// a segment is neither axis-aligned nor diagonal at 45°.
+//
+// Overlaps along a line are found by sorting the endpoints on that line, and
+// crossings between lines of different directions by a sweep over each pair of
+// directions, so the work is O((n + crossings) log n) however long the
+// segments are.
+pub fn count_overlaps(segments: &[LineSegment<i32>], k: usize) -> Option<usize> {
+    let pieces = coverage(segments)?;
+    let by_family: Vec<Vec<Piece>> = Family::ALL
+        .iter()
+        .map(|&family| {
+            pieces
+                .iter()
+                .filter(|piece| piece.family == family)
+                .copied()
+                .collect()
+        })
+        .collect();
+
+    // Points on pieces of several families are tallied separately; the runs
+    // themselves are counted as if every point were on one line only.
+    let mut shared = HashMap::<Point<i64>, [usize; 4]>::new();
+    for i in 0..Family::ALL.len() {
+        for j in i + 1..Family::ALL.len() {
+            crossings(&by_family[i], &by_family[j], |p, a, b| {
+                let counts = shared.entry(p).or_default();
+                counts[i] = a.count;
+                counts[j] = b.count;
+            });
+        }
+    }
+
+    let mut total: usize = pieces
+        .iter()
+        .filter(|piece| piece.count >= k)
+        .map(|piece| (piece.hi - piece.lo + 1) as usize)
+        .sum();
+    for counts in shared.values() {
+        let over = counts.iter().filter(|&&count| count >= k).count();
+        let hit = (counts.iter().sum::<usize>() >= k) as usize;
+        total = total + hit - over;
+    }
+    Some(total)
+}
+
+pub struct Day5;
+
+impl Solver for Day5 {
+    type Input = Vec<LineSegment<i32>>;
+    type Error = AnyError;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        Ok(parsing_input_strict(input.as_bytes()).collect::<Result<_, _>>()?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        let aligned: Vec<_> = input.iter().copied().filter(is_axis_aligned).collect();
+        Ok(count_overlaps(&aligned, 2).ok_or("segments must be axis-aligned or diagonal")?)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        Ok(count_overlaps(input, 2).ok_or("segments must be axis-aligned or diagonal")?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The brute-force oracle: rasterizes every segment into a map of points.
+    fn raster_counts<I>(segments: I) -> HashMap<Point<i32>, usize>
+    where
+        I: IntoIterator<Item = LineSegment<i32>>,
+    {
+        let mut counts = HashMap::new();
+        for p in segments.into_iter().flat_map(|line| line.lattice_points()) {
+            *counts.entry(p).or_default() += 1;
+        }
+        counts
+    }
+
+    fn xed_points_at_least(segments: &[LineSegment<i32>], k: usize) -> usize {
+        raster_counts(segments.iter().copied())
+            .values()
+            .filter(|&&count| count >= k)
+            .count()
+    }
+
+    fn example_segments() -> Vec<LineSegment<i32>> {
+        vec![
+            LineSegment::new((0, 9), (5, 9)),
+            LineSegment::new((8, 0), (0, 8)),
+            LineSegment::new((9, 4), (3, 4)),
+            LineSegment::new((2, 2), (2, 1)),
+            LineSegment::new((7, 0), (7, 4)),
+            LineSegment::new((6, 4), (2, 0)),
+            LineSegment::new((0, 9), (2, 9)),
+            LineSegment::new((3, 4), (1, 4)),
+            LineSegment::new((0, 0), (8, 8)),
+            LineSegment::new((5, 5), (8, 2)),
+        ]
+    }
+
+    #[test]
+    fn test_count_overlaps_example() {
+        let segments = example_segments();
+        let aligned: Vec<_> = segments.iter().copied().filter(is_axis_aligned).collect();
+        assert_eq!(count_overlaps(&aligned, 2), Some(5));
+        assert_eq!(count_overlaps(&segments, 2), Some(12));
+        assert_eq!(count_overlaps(&segments, 3), Some(2));
+        assert_eq!(
+            count_overlaps(&segments, 1),
+            Some(xed_points_at_least(&segments, 1))
+        );
+
+        let skewed = [LineSegment::new((0, 0), (2, 1))];
+        assert_eq!(count_overlaps(&skewed, 1), None);
+    }
+
+    #[test]
+    fn test_count_overlaps_matches_rasterizing() {
+        // a dense tangle of every supported direction, including collinear
+        // overlaps, shared endpoints, off-lattice diagonal crossings and points
+        let mut segments = Vec::new();
+        let mut seed = 7u32;
+        let mut next = |n: u32| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            ((seed >> 16) % n) as i32
+        };
+        for _ in 0..200 {
+            let (x, y, len) = (next(30), next(30), next(12));
+            let end = match next(5) {
+                0 => (x + len, y),
+                1 => (x, y - len),
+                2 => (x + len, y + len),
+                3 => (x - len, y + len),
+                _ => (x, y),
+            };
+            segments.push(LineSegment::new((x, y), end));
+        }
+
+        for k in 1..=5 {
+            assert_eq!(
+                count_overlaps(&segments, k),
+                Some(xed_points_at_least(&segments, k)),
+                "k = {}",
+                k
+            );
+        }
+    }
+
+    #[test]
+    fn test_count_overlaps_long_segments() {
+        let big = 1_000_000_000;
+        let segments = [
+            LineSegment::new((-big, 0), (big, 0)),
+            LineSegment::new((0, -big), (0, big)),
+            LineSegment::new((-big, -big), (big, big)),
+            LineSegment::new((-big, big), (big, -big)),
+            LineSegment::new((1, 1 - big), (1, big - 1)),
+            LineSegment::new((0, 5), (0, big + 1)),
+        ];
+        // the origin, where the x axis, both diagonals and the y axis meet;
+        // (1, 0), (1, 1) and (1, -1), where x = 1 crosses them; and the
+        // overlap on the y axis
+        assert_eq!(count_overlaps(&segments, 2), Some(4 + (big as usize - 4)));
+        assert_eq!(count_overlaps(&segments, 4), Some(1));
+    }
+}
@@ -1,42 +1,20 @@
-use core::fmt::Debug;
+use crate::solver::Solver;
+
+use core::num::ParseIntError;
 use core::ops::Index;
 use core::ops::IndexMut;
-use std::io::BufRead;
 
 use core::str::FromStr;
 
-#[derive(Debug)]
-pub enum ParseInputError<T: FromStr>
-where
-    <T as FromStr>::Err: Debug,
-{
-    InnerType(<T as FromStr>::Err),
-    IO(std::io::Error),
-}
-
-pub fn parse_input<R: BufRead, T: FromStr>(mut reader: R) -> Result<Vec<T>, ParseInputError<T>>
-where
-    <T as FromStr>::Err: Debug,
-{
-    let mut buffer = String::new();
-    reader.read_line(&mut buffer).map_err(ParseInputError::IO)?;
-
-    buffer
-        .split(',')
-        .map(T::from_str)
-        .map(|r| r.map_err(ParseInputError::InnerType))
-        .collect()
-}
-
 #[derive(Debug, Copy, Clone, Hash)]
-struct RollingBuffer<T, const LEN: usize>([T; LEN], usize);
+pub struct RollingBuffer<T, const LEN: usize>([T; LEN], usize);
 
 impl<T, const LEN: usize> RollingBuffer<T, LEN> {
-    fn new(array: [T; LEN]) -> Self {
+    pub fn new(array: [T; LEN]) -> Self {
         Self(array, 0)
     }
 
-    fn roll(&mut self, index: usize) {
+    pub fn roll(&mut self, index: usize) {
         self.1 = (self.1 + index) % LEN;
     }
 }
@@ -54,11 +32,11 @@ impl<T, const LEN: usize> IndexMut<usize> for RollingBuffer<T, LEN> {
     }
 }
 
-fn simulate_lanternfish<I>(iter: I) -> impl Iterator<Item = RollingBuffer<usize, 9>>
+pub fn simulate_lanternfish<I>(iter: I) -> impl Iterator<Item = RollingBuffer<usize, 9>>
 where
     I: Iterator<Item = usize>,
 {
-    let mut counter = RollingBuffer([0; 9], 0);
+    let mut counter = RollingBuffer::new([0; 9]);
     for i in iter {
         counter[i] += 1;
     }
@@ -71,6 +49,39 @@ where
     }))
 }
 
+pub fn count_simfish<I>(iter: I, epochs: usize) -> usize
+where
+    I: Iterator<Item = usize>,
+{
+    simulate_lanternfish(iter)
+        .nth(epochs)
+        .expect("simulation never terminates")
+        .0
+        .iter()
+        .sum()
+}
+
+pub struct Day6;
+
+impl Solver for Day6 {
+    type Input = Vec<usize>;
+    type Error = ParseIntError;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        input.trim().split(',').map(usize::from_str).collect()
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Ok(count_simfish(input.iter().copied(), 80))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        Ok(count_simfish(input.iter().copied(), 256))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -105,28 +116,13 @@ mod tests {
     #[test]
     fn test_count_simfish_example3() {
         let sequence = vec![3, 4, 3, 1, 2];
-        let calc_result: usize = simulate_lanternfish(sequence.into_iter())
-            .nth(256)
-            .unwrap()
-            .0
-            .iter()
-            .sum();
-
-        assert_eq!(calc_result, 26984457539);
+        assert_eq!(count_simfish(sequence.into_iter(), 256), 26984457539);
     }
-}
-
-fn main() {
-    println!("Enter input:");
-    let stdin = std::io::stdin();
-    let parsed_inputs = parse_input::<_, usize>(stdin.lock()).unwrap();
 
-    let n = 256;
-    let count_simfish: usize = simulate_lanternfish(parsed_inputs.into_iter())
-        .nth(n)
-        .unwrap()
-        .0
-        .iter()
-        .sum();
-    println!("{:?}th epoch fish count: {:?}", n, count_simfish);
+    #[test]
+    fn test_solver_example() {
+        let input = Day6::parse("3,4,3,1,2\n").unwrap();
+        assert_eq!(Day6::part1(&input), Ok(5934));
+        assert_eq!(Day6::part2(&input), Ok(26984457539));
+    }
 }
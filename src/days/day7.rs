@@ -0,0 +1,145 @@
+use crate::num::triangular;
+use crate::solver::Solver;
+
+use core::num::ParseIntError;
+use core::str::FromStr;
+
+pub fn crab_fuel<I>(locs: I) -> Vec<usize>
+where
+    I: Iterator<Item = usize>,
+{
+    let loc_counts = crab_loc_counts(locs);
+    let n: usize = loc_counts.len();
+
+    let prefix_sums: Vec<usize> = {
+        let mut sum = 0;
+        std::iter::once(&0)
+            .chain(loc_counts.iter())
+            .map(move |item| {
+                sum += item;
+                sum
+            })
+    }
+    .collect();
+    let whole_sum = prefix_sums[n];
+
+    let zero_index_score: usize = loc_counts
+        .iter()
+        .enumerate()
+        .map(|(i, &count)| i * count)
+        .sum();
+
+    {
+        let mut sum = zero_index_score + whole_sum;
+        prefix_sums.into_iter().map(move |item| {
+            sum += 2 * item;
+            sum -= whole_sum;
+            sum
+        })
+    }
+    .collect()
+}
+
+pub fn crab_loc_counts<I>(locs: I) -> Vec<usize>
+where
+    I: Iterator<Item = usize>,
+{
+    locs.fold(Vec::new(), |mut loc_cntr, i| {
+        if i >= loc_cntr.len() {
+            loc_cntr.resize_with(i + 1, Default::default);
+        }
+        loc_cntr[i] += 1;
+        loc_cntr
+    })
+}
+
+pub fn crab_fuel_cost(loc_counts: &[usize], center_loc: usize) -> usize {
+    loc_counts
+        .iter()
+        .enumerate()
+        .map(|(i, count)| (i.abs_diff(center_loc), count))
+        .fold(0, |sum, (i, &count)| sum + triangular(i) * count)
+}
+
+pub fn min_crab_fuel_cost(loc_counts: &[usize]) -> (usize, usize) {
+    let sum: usize = loc_counts
+        .iter()
+        .enumerate()
+        .map(|(i, &count)| i * count)
+        .sum();
+    let count: usize = loc_counts.iter().sum();
+    let i_mean = sum / count;
+
+    let score1 = crab_fuel_cost(loc_counts, i_mean);
+    let score2 = crab_fuel_cost(loc_counts, i_mean + 1);
+
+    if score1 <= score2 {
+        (i_mean, score1)
+    } else {
+        (i_mean + 1, score2)
+    }
+}
+
+pub struct Day7;
+
+impl Solver for Day7 {
+    type Input = Vec<usize>;
+    type Error = ParseIntError;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        input.trim().split(',').map(usize::from_str).collect()
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Ok(crab_fuel(input.iter().copied())
+            .into_iter()
+            .min()
+            .expect("parsing guarantees at least one crab"))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        let crab_locs = crab_loc_counts(input.iter().copied());
+        let (_loc, crab_fuel) = min_crab_fuel_cost(&crab_locs);
+        Ok(crab_fuel)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crab_fuel_example1() {
+        let sequence = vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+        assert_eq!(
+            crab_fuel(sequence.into_iter()),
+            vec![49, 41, 37, 39, 41, 45, 49, 53, 59, 65, 71, 77, 83, 89, 95, 103, 111, 121]
+        );
+    }
+
+    #[test]
+    fn test_min_crab_fuel_example1() {
+        let sequence = vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+        let crab_locs = crab_loc_counts(sequence.into_iter());
+        let calc_result = min_crab_fuel_cost(&crab_locs);
+        assert_eq!(calc_result, (5, 168));
+    }
+
+    #[test]
+    fn test_min_crab_fuel_example2() {
+        let sequence = vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+        let crab_locs = crab_loc_counts(sequence.into_iter());
+        let calc_result = crab_fuel_cost(&crab_locs, 2);
+        assert_eq!(calc_result, 206);
+    }
+
+    #[test]
+    fn test_solver_example() {
+        let input = Day7::parse("16,1,2,0,4,2,7,1,2,14\n").unwrap();
+        assert_eq!(Day7::part1(&input), Ok(37));
+        assert_eq!(Day7::part2(&input), Ok(168));
+        assert!(Day7::parse("16,1,x").is_err());
+    }
+}
@@ -0,0 +1,211 @@
+use crate::parse::{finish, PResult, ParseError};
+use crate::solver::Solver;
+use crate::utils::{parsing_input_strict, AnyError, TryCollect};
+
+use core::convert::TryFrom;
+use core::str::FromStr;
+use nom::bytes::complete::tag;
+use nom::character::complete::{one_of, space1};
+use nom::combinator::{map, map_opt, map_res};
+use nom::error::context;
+use nom::multi::{many1, separated_list1};
+use nom::sequence::separated_pair;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, std::hash::Hash)]
+pub struct SevenSegmentGroup(u8);
+
+fn segment_group(input: &str) -> PResult<'_, SevenSegmentGroup> {
+    let segments = many1(one_of("abcdefg"));
+    context(
+        "distinct segments a-g",
+        map_opt(segments, |chars| {
+            chars
+                .into_iter()
+                .try_fold(0_u8, |result, c| {
+                    let char_bit = 1 << ((c as u8) - b'a');
+                    (result & char_bit == 0).then_some(result | char_bit)
+                })
+                .map(SevenSegmentGroup)
+        }),
+    )(input)
+}
+
+fn segment_groups<const N: usize>(input: &str) -> PResult<'_, [SevenSegmentGroup; N]> {
+    map_res(
+        separated_list1(space1, segment_group),
+        <[SevenSegmentGroup; N]>::try_from,
+    )(input)
+}
+
+impl FromStr for SevenSegmentGroup {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        finish(s, segment_group)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InputWrapper([SevenSegmentGroup; 10], [SevenSegmentGroup; 4]);
+
+impl FromStr for InputWrapper {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let wrapper = separated_pair(
+            context("10 signal patterns", segment_groups::<10>),
+            tag(" | "),
+            context("4 output digits", segment_groups::<4>),
+        );
+        finish(s, map(wrapper, |(v1, v2)| InputWrapper(v1, v2)))
+    }
+}
+
+pub fn count_1478<I>(iter: I) -> usize
+where
+    I: Iterator<Item = InputWrapper>,
+{
+    iter.map(|w| {
+        IntoIterator::into_iter(w.1)
+            .filter(|seg| [2, 3, 4, 7].contains(&seg.0.count_ones()))
+            .count()
+    })
+    .sum()
+}
+
+pub fn make_decoder(
+    seg_groups: [SevenSegmentGroup; 10],
+) -> Result<[SevenSegmentGroup; 10], &'static str> {
+    const LENS_1478: [u32; 4] = [2, 3, 4, 7];
+    let mut result: [Option<SevenSegmentGroup>; 10] = [Default::default(); 10];
+
+    let (group_matches, others): (Vec<_>, Vec<_>) = IntoIterator::into_iter(seg_groups)
+        .partition(|seg_group| LENS_1478.contains(&seg_group.0.count_ones()));
+    for seg_group in group_matches.into_iter() {
+        let map_value = match seg_group.0.count_ones() {
+            2 => 1,
+            3 => 7,
+            4 => 4,
+            7 => 8,
+            _ => unreachable!("already confirmed that segment count is one of [2, 3, 4, 7]"),
+        };
+        if result[map_value].is_some() {
+            return Err("encountered overlapping segment groups");
+        }
+        result[map_value] = Some(seg_group);
+    }
+
+    for seg_group in others.into_iter() {
+        let bits = seg_group.0;
+        let map_value = match (
+            bits.count_ones(),
+            (bits & result[1].ok_or("digit 1 format not found")?.0).count_ones(),
+            (bits & result[4].ok_or("digit 4 format not found")?.0).count_ones(),
+        ) {
+            (5, 1, 2) => 2,
+            (5, 2, 3) => 3,
+            (5, 1, 3) => 5,
+            (6, 2, 3) => 0,
+            (6, 1, 3) => 6,
+            (6, 2, 4) => 9,
+            _ => return Err("invalid segment group intersections"),
+        };
+
+        if result[map_value].is_some() {
+            return Err("encountered overlapping segment groups");
+        }
+        result[map_value] = Some(seg_group);
+    }
+
+    IntoIterator::into_iter(result)
+        .map(|o| o.ok_or("uninitialized digit"))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .try_collect_into()
+        .map_err(|_| "wrong number of digits")
+}
+
+pub fn decode_display<I>(
+    decoder: &[SevenSegmentGroup; 10],
+    digit_seg_groups: I,
+) -> Result<u64, &'static str>
+where
+    I: Iterator<Item = SevenSegmentGroup>,
+{
+    let mut result = 0;
+    for seg_group in digit_seg_groups {
+        result = 10 * result
+            + decoder
+                .iter()
+                .enumerate()
+                .find_map(|(i, &seg_format)| (seg_format == seg_group).then_some(i as u64))
+                .ok_or("segment group not found in decoder")?;
+    }
+    Ok(result)
+}
+
+pub struct Day8;
+
+impl Solver for Day8 {
+    type Input = Vec<InputWrapper>;
+    type Error = AnyError;
+    type Answer1 = usize;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        let lines = parsing_input_strict(input.as_bytes()).collect::<Result<_, _>>()?;
+        Ok(lines)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Ok(count_1478(input.iter().cloned()))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        let mut result: u64 = 0;
+        for InputWrapper(segs, display) in input {
+            let decoder = make_decoder(*segs)?;
+            result += decode_display(&decoder, display.iter().copied())?;
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_display_example() {
+        let wrapper = InputWrapper::from_str(
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf",
+        )
+        .unwrap();
+        assert_eq!(count_1478(std::iter::once(wrapper)), 0);
+
+        let InputWrapper(segs, display) = InputWrapper::from_str(
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf",
+        )
+        .unwrap();
+        let decoder = make_decoder(segs).unwrap();
+        assert_eq!(
+            decode_display(&decoder, IntoIterator::into_iter(display)),
+            Ok(5353)
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = InputWrapper::from_str("aba cd | ab").unwrap_err();
+        assert_eq!(
+            (err.column, err.expected.as_str()),
+            (1, "distinct segments a-g")
+        );
+
+        let err = InputWrapper::from_str("ab cd | ab cd ef ga").unwrap_err();
+        assert_eq!(
+            (err.column, err.expected.as_str()),
+            (1, "10 signal patterns")
+        );
+    }
+}
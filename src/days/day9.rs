@@ -1,31 +1,31 @@
-use aoc_lib::grid::{adj4_coords, read_input, Grid};
-use aoc_lib::utils::n_min;
+use crate::grid::Grid;
+use crate::solver::Solver;
+use crate::utils::n_min;
 
-fn find_low_points<const ROW: usize, const COL: usize>(
-    cave_map: &Grid<u32, ROW, COL>,
-) -> Grid<bool, ROW, COL> {
+pub fn find_low_points(cave_map: &Grid<u32>) -> Grid<bool> {
     use std::cmp::Ordering::*;
 
-    let mut result = [[true; COL]; ROW];
+    let mut result = Grid::new(cave_map.height(), cave_map.width(), true);
 
-    for (res_row, cave_row) in (&mut result[..]).iter_mut().zip(&cave_map[..]) {
-        for j in 0..(COL - 1) {
-            match cave_row[j].cmp(&cave_row[j + 1]) {
-                Less => res_row[j + 1] = false,
-                Greater => res_row[j] = false,
+    for i in 0..cave_map.height() {
+        let (cave_row, res_row) = (cave_map.row(i), result.row_mut(i));
+        for j in 1..cave_map.width() {
+            match cave_row[j - 1].cmp(&cave_row[j]) {
+                Less => res_row[j] = false,
+                Greater => res_row[j - 1] = false,
                 _ => {
+                    res_row[j - 1] = false;
                     res_row[j] = false;
-                    res_row[j + 1] = false;
                 }
             }
         }
     }
 
-    for j in 0..COL {
-        for i in 0..(ROW - 1) {
-            match cave_map[i][j].cmp(&cave_map[i + 1][j]) {
-                Less => result[i + 1][j] = false,
-                Greater => result[i][j] = false,
+    for j in 0..cave_map.width() {
+        for i in 1..cave_map.height() {
+            match cave_map[(i - 1, j)].cmp(&cave_map[(i, j)]) {
+                Less => result[(i, j)] = false,
+                Greater => result[(i - 1, j)] = false,
                 _ => (),
             }
         }
@@ -34,22 +34,24 @@ fn find_low_points<const ROW: usize, const COL: usize>(
     result
 }
 
-fn risk_levels<'a, const ROW: usize, const COL: usize>(
-    cave_map: &'a Grid<u32, ROW, COL>,
-    low_points: &'a Grid<bool, ROW, COL>,
+pub fn risk_levels<'a>(
+    cave_map: &'a Grid<u32>,
+    low_points: &'a Grid<bool>,
 ) -> impl 'a + Iterator<Item = u32> {
-    (0..ROW)
-        .flat_map(|i| (0..COL).map(move |j| (i, j)))
-        .filter_map(move |(i, j)| low_points[i][j].then(|| 1 + cave_map[i][j]))
+    cave_map
+        .cells()
+        .filter(move |&(coord, _)| low_points[coord])
+        .map(|(_, height)| 1 + height)
 }
 
-fn iter_basins<const ROW: usize, const COL: usize>(
-    cave_map: &Grid<u32, ROW, COL>,
-    mut low_points: Grid<bool, ROW, COL>,
+pub fn iter_basins(
+    cave_map: &Grid<u32>,
+    mut low_points: Grid<bool>,
 ) -> impl '_ + Iterator<Item = usize> {
-    let iter_low_points = (0..ROW)
-        .flat_map(|i| (0..COL).map(move |j| (i, j)))
-        .filter(|(i, j)| low_points[*i][*j])
+    let iter_low_points = low_points
+        .cells()
+        .filter(|&(_, &is_low)| is_low)
+        .map(|(coord, _)| coord)
         .collect::<Vec<_>>();
 
     let mut point_buffer = Vec::new();
@@ -60,15 +62,12 @@ fn iter_basins<const ROW: usize, const COL: usize>(
         while let Some(coord) = point_buffer.pop() {
             count += 1;
 
-            for (i, j) in adj4_coords::<ROW, COL>(coord) {
-                if low_points[i][j]
-                    || cave_map[i][j] >= 9
-                    || cave_map[i][j] < cave_map[coord.0][coord.1]
-                {
+            for adj in cave_map.adj4(coord) {
+                if low_points[adj] || cave_map[adj] >= 9 || cave_map[adj] < cave_map[coord] {
                     continue;
                 }
-                point_buffer.push((i, j));
-                low_points[i][j] = true;
+                point_buffer.push(adj);
+                low_points[adj] = true;
             }
         }
 
@@ -76,41 +75,65 @@ fn iter_basins<const ROW: usize, const COL: usize>(
     })
 }
 
+pub struct Day9;
+
+impl Solver for Day9 {
+    type Input = Grid<u32>;
+    type Error = &'static str;
+    type Answer1 = u32;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error> {
+        Grid::from_digits(input)
+    }
+
+    fn part1(cave_map: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        let low_points = find_low_points(cave_map);
+        Ok(risk_levels(cave_map, &low_points).sum())
+    }
+
+    fn part2(cave_map: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        let low_points = find_low_points(cave_map);
+        let basins = n_min(3, iter_basins(cave_map, low_points).map(std::cmp::Reverse));
+        Ok(basins.into_iter().map(|r| r.0).product())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_low_points_example() {
-        let cave_map = [
+        let cave_map = Grid::from([
             [2, 1, 9, 9, 9, 4, 3, 2, 1, 0],
             [3, 9, 8, 7, 8, 9, 4, 9, 2, 1],
             [9, 8, 5, 6, 7, 8, 9, 8, 9, 2],
             [8, 7, 6, 7, 8, 9, 6, 7, 8, 9],
             [9, 8, 9, 9, 9, 6, 5, 6, 7, 8],
-        ];
+        ]);
         let low_points = find_low_points(&cave_map);
         assert_eq!(
             low_points,
-            [
+            Grid::from([
                 [false, true, false, false, false, false, false, false, false, true],
                 [false, false, false, false, false, false, false, false, false, false],
                 [false, false, true, false, false, false, false, false, false, false],
                 [false, false, false, false, false, false, false, false, false, false],
                 [false, false, false, false, false, false, true, false, false, false],
-            ]
+            ])
         );
     }
 
     #[test]
     fn test_risk_levels_example() {
-        let cave_map = [
+        let cave_map = Grid::from([
             [2, 1, 9, 9, 9, 4, 3, 2, 1, 0],
             [3, 9, 8, 7, 8, 9, 4, 9, 2, 1],
             [9, 8, 5, 6, 7, 8, 9, 8, 9, 2],
             [8, 7, 6, 7, 8, 9, 6, 7, 8, 9],
             [9, 8, 9, 9, 9, 6, 5, 6, 7, 8],
-        ];
+        ]);
         let low_points = find_low_points(&cave_map);
         assert_eq!(
             risk_levels(&cave_map, &low_points).collect::<Vec<_>>(),
@@ -120,13 +143,13 @@ mod tests {
 
     #[test]
     fn test_iter_basins_example() {
-        let cave_map = [
+        let cave_map = Grid::from([
             [2, 1, 9, 9, 9, 4, 3, 2, 1, 0],
             [3, 9, 8, 7, 8, 9, 4, 9, 2, 1],
             [9, 8, 5, 6, 7, 8, 9, 8, 9, 2],
             [8, 7, 6, 7, 8, 9, 6, 7, 8, 9],
             [9, 8, 9, 9, 9, 6, 5, 6, 7, 8],
-        ];
+        ]);
         let low_points = find_low_points(&cave_map);
         assert_eq!(
             iter_basins(&cave_map, low_points).collect::<Vec<_>>(),
@@ -134,17 +157,3 @@ mod tests {
         );
     }
 }
-
-fn main() {
-    println!("Enter input:");
-    let stdin = std::io::stdin();
-
-    let cave_map = read_input::<_, 100, 100>(stdin.lock()).unwrap();
-    let low_points = find_low_points(&cave_map);
-    let basins: Vec<_> = n_min(3, iter_basins(&cave_map, low_points).map(std::cmp::Reverse))
-        .into_iter()
-        .map(|r| r.0)
-        .collect();
-    println!("basins: {:?}", basins);
-    println!("basin product: {:?}", basins.into_iter().product::<usize>());
-}
@@ -0,0 +1,79 @@
+use crate::solver::{solve_part1, solve_part2};
+use crate::utils::AnyError;
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+pub type PartFn = fn(&str) -> Result<String, AnyError>;
+
+#[derive(Debug, Copy, Clone)]
+pub struct Solution {
+    pub day: u32,
+    pub part: u32,
+    pub solve: PartFn,
+}
+
+macro_rules! register {
+    ($($day:literal => $solver:path),* $(,)?) => {
+        pub const REGISTRY: &[Solution] = &[
+            $(
+                Solution { day: $day, part: 1, solve: solve_part1::<$solver> },
+                Solution { day: $day, part: 2, solve: solve_part2::<$solver> },
+            )*
+        ];
+    };
+}
+
+register! {
+    1 => day1::Day1,
+    2 => day2::Day2,
+    3 => day3::Day3,
+    4 => day4::Day4,
+    5 => day5::Day5,
+    6 => day6::Day6,
+    7 => day7::Day7,
+    8 => day8::Day8,
+    9 => day9::Day9,
+    10 => day10::Day10,
+    11 => day11::Day11,
+    12 => day12::Day12,
+    13 => day13::Day13,
+    14 => day14::Day14,
+    15 => day15::Day15,
+    16 => day16::Day16,
+}
+
+pub fn find(day: u32, part: u32) -> Option<&'static Solution> {
+    REGISTRY.iter().find(|s| s.day == day && s.part == part)
+}
+
+pub fn for_day(day: u32) -> impl Iterator<Item = &'static Solution> {
+    REGISTRY.iter().filter(move |s| s.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_unique_entries() {
+        let mut keys: Vec<_> = REGISTRY.iter().map(|s| (s.day, s.part)).collect();
+        keys.sort_unstable();
+        keys.dedup();
+        assert_eq!(keys.len(), REGISTRY.len());
+    }
+}
@@ -1,12 +1,17 @@
 use itertools::Itertools;
 
-use crate::utils::ArrayWrapper;
-use std::io::BufRead;
+use core::ops::{Index, IndexMut};
 
-pub type Grid<T, const ROW: usize, const COL: usize> = [[T; COL]; ROW];
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
 
-pub fn adj4_coords<const ROW: usize, const COL: usize>(
+pub fn adj4_coords(
     center: (usize, usize),
+    (height, width): (usize, usize),
 ) -> impl 'static + Iterator<Item = (usize, usize)> {
     let mut result = Vec::new();
 
@@ -16,18 +21,19 @@ pub fn adj4_coords<const ROW: usize, const COL: usize>(
     if let Some(offset_1) = center.1.checked_sub(1) {
         result.push((center.0, offset_1));
     }
-    if center.0 < ROW - 1 {
+    if center.0 + 1 < height {
         result.push((center.0 + 1, center.1));
     }
-    if center.1 < COL - 1 {
+    if center.1 + 1 < width {
         result.push((center.0, center.1 + 1));
     }
 
     result.into_iter()
 }
 
-pub fn adj8_coords<const ROW: usize, const COL: usize>(
+pub fn adj8_coords(
     center: (usize, usize),
+    (height, width): (usize, usize),
 ) -> impl 'static + Iterator<Item = (usize, usize)> {
     let mut i_vals = vec![center.0];
     let mut j_vals = vec![center.1];
@@ -38,33 +44,320 @@ pub fn adj8_coords<const ROW: usize, const COL: usize>(
     if let Some(offset_j) = center.1.checked_sub(1) {
         j_vals.push(offset_j);
     }
-    if center.0 < ROW - 1 {
+    if center.0 + 1 < height {
         i_vals.push(center.0 + 1);
     }
-    if center.1 < COL - 1 {
+    if center.1 + 1 < width {
         j_vals.push(center.1 + 1);
     }
 
-    i_vals
-        .into_iter()
-        .cartesian_product(j_vals.into_iter())
-        .skip(1)
+    i_vals.into_iter().cartesian_product(j_vals).skip(1)
+}
+
+impl<T> Grid<T> {
+    pub fn new(height: usize, width: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    pub fn from_rows<I, R>(rows: I) -> Result<Self, &'static str>
+    where
+        I: IntoIterator<Item = R>,
+        R: IntoIterator<Item = T>,
+    {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+
+        for row in rows {
+            let row_start = cells.len();
+            cells.extend(row);
+            let row_len = cells.len() - row_start;
+
+            if *width.get_or_insert(row_len) != row_len {
+                return Err("inconsistent row length");
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or_default(),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn dims(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, (i, j): (usize, usize)) -> bool {
+        i < self.height && j < self.width
+    }
+
+    pub fn get(&self, coord: (usize, usize)) -> Option<&T> {
+        self.contains(coord)
+            .then(|| &self.cells[coord.0 * self.width + coord.1])
+    }
+
+    pub fn get_mut(&mut self, coord: (usize, usize)) -> Option<&mut T> {
+        if self.contains(coord) {
+            Some(&mut self.cells[coord.0 * self.width + coord.1])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.cells[(i * self.width)..((i + 1) * self.width)]
+    }
+
+    pub fn row_mut(&mut self, i: usize) -> &mut [T] {
+        &mut self.cells[(i * self.width)..((i + 1) * self.width)]
+    }
+
+    pub fn rows(&self) -> impl '_ + Iterator<Item = &[T]> {
+        (0..self.height).map(move |i| self.row(i))
+    }
+
+    pub fn column(&self, j: usize) -> impl '_ + Iterator<Item = &T> {
+        assert!(j < self.width, "column index out of bounds");
+        self.cells.iter().skip(j).step_by(self.width)
+    }
+
+    pub fn iter(&self) -> impl '_ + Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl '_ + Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn coords(&self) -> impl 'static + Iterator<Item = (usize, usize)> {
+        (0..self.height).cartesian_product(0..self.width)
+    }
+
+    pub fn cells(&self) -> impl '_ + Iterator<Item = ((usize, usize), &T)> {
+        self.coords().zip(self.cells.iter())
+    }
+
+    pub fn adj4(&self, center: (usize, usize)) -> impl 'static + Iterator<Item = (usize, usize)> {
+        adj4_coords(center, self.dims())
+    }
+
+    pub fn adj8(&self, center: (usize, usize)) -> impl 'static + Iterator<Item = (usize, usize)> {
+        adj8_coords(center, self.dims())
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, func: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(func).collect(),
+        }
+    }
 }
 
-pub fn read_input<R: BufRead, const ROW: usize, const COL: usize>(
-    reader: R,
-) -> Result<Grid<u32, ROW, COL>, &'static str> {
-    reader
-        .lines()
-        .map(|r| r.map_err(|_| "bad line read"))
-        .map(|r| -> Result<_, &'static str> {
-            r.and_then(|s| {
-                s.chars()
+impl Grid<u32> {
+    pub fn from_digits(s: &str) -> Result<Self, &'static str> {
+        let grid = s
+            .trim_end()
+            .lines()
+            .map(|line| {
+                line.chars()
                     .map(|c| c.to_digit(10).ok_or("bad digit"))
-                    .collect::<Result<ArrayWrapper<_, COL>, _>>()
-                    .map(From::from)
+                    .collect::<Result<Vec<_>, _>>()
             })
-        })
-        .collect::<Result<ArrayWrapper<_, ROW>, _>>()
-        .map(From::from)
+            .collect::<Result<Vec<_>, _>>()
+            .and_then(Self::from_rows)?;
+
+        if grid.is_empty() {
+            return Err("no digits in input");
+        }
+        Ok(grid)
+    }
+}
+
+impl<T, const ROW: usize, const COL: usize> From<[[T; COL]; ROW]> for Grid<T> {
+    fn from(array: [[T; COL]; ROW]) -> Self {
+        Self {
+            width: COL,
+            height: ROW,
+            cells: IntoIterator::into_iter(array).flatten().collect(),
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: (usize, usize)) -> &Self::Output {
+        self.get(coord).expect("grid index out of bounds")
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, coord: (usize, usize)) -> &mut Self::Output {
+        self.get_mut(coord).expect("grid index out of bounds")
+    }
+}
+
+pub struct TiledGrid<'a, T, F> {
+    base: &'a Grid<T>,
+    tiles: (usize, usize),
+    rule: F,
+}
+
+impl<'a, T, F: Fn(&T, (usize, usize)) -> T> TiledGrid<'a, T, F> {
+    pub fn new(base: &'a Grid<T>, tiles: (usize, usize), rule: F) -> Self {
+        Self { base, tiles, rule }
+    }
+
+    pub fn base(&self) -> &'a Grid<T> {
+        self.base
+    }
+
+    pub fn tiles(&self) -> (usize, usize) {
+        self.tiles
+    }
+
+    pub fn width(&self) -> usize {
+        self.tiles.1 * self.base.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.tiles.0 * self.base.height()
+    }
+
+    pub fn dims(&self) -> (usize, usize) {
+        (self.height(), self.width())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width() == 0 || self.height() == 0
+    }
+
+    pub fn contains(&self, (i, j): (usize, usize)) -> bool {
+        i < self.height() && j < self.width()
+    }
+
+    pub fn get(&self, (i, j): (usize, usize)) -> Option<T> {
+        if !self.contains((i, j)) {
+            return None;
+        }
+        let (row, col) = self.base.dims();
+        let value = &self.base[(i % row, j % col)];
+        Some((self.rule)(value, (i / row, j / col)))
+    }
+
+    pub fn coords(&self) -> impl 'static + Iterator<Item = (usize, usize)> {
+        (0..self.height()).cartesian_product(0..self.width())
+    }
+
+    pub fn adj4(&self, center: (usize, usize)) -> impl 'static + Iterator<Item = (usize, usize)> {
+        adj4_coords(center, self.dims())
+    }
+
+    pub fn adj8(&self, center: (usize, usize)) -> impl 'static + Iterator<Item = (usize, usize)> {
+        adj8_coords(center, self.dims())
+    }
+
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid {
+            width: self.width(),
+            height: self.height(),
+            cells: self
+                .coords()
+                .map(|coord| self.get(coord).expect("coords are in bounds"))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_digits() {
+        let grid = Grid::from_digits("123\n456\n").unwrap();
+
+        assert_eq!((grid.height(), grid.width()), (2, 3));
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid, Grid::from([[1, 2, 3], [4, 5, 6]]));
+
+        assert_eq!(Grid::from_digits("12\n345"), Err("inconsistent row length"));
+        assert_eq!(Grid::from_digits("12\n3x"), Err("bad digit"));
+        assert_eq!(Grid::from_digits(""), Err("no digits in input"));
+    }
+
+    #[test]
+    fn test_adj_coords() {
+        let grid = Grid::new(3, 4, 0);
+
+        let mut adj = grid.adj4((0, 3)).collect::<Vec<_>>();
+        adj.sort_unstable();
+        assert_eq!(adj, vec![(0, 2), (1, 3)]);
+
+        let mut adj = grid.adj8((1, 1)).collect::<Vec<_>>();
+        adj.sort_unstable();
+        assert_eq!(
+            adj,
+            vec![
+                (0, 0),
+                (0, 1),
+                (0, 2),
+                (1, 0),
+                (1, 2),
+                (2, 0),
+                (2, 1),
+                (2, 2)
+            ]
+        );
+        assert_eq!(grid.adj8((2, 3)).count(), 3);
+    }
+
+    #[test]
+    fn test_tiled_grid() {
+        let grid = Grid::from([[1, 2], [3, 4]]);
+        let tiled = TiledGrid::new(&grid, (3, 7), |&v, (ti, tj)| v + 10 * ti + 100 * tj);
+
+        assert_eq!(tiled.dims(), (6, 14));
+        assert_eq!(tiled.get((0, 0)), Some(1));
+        assert_eq!(tiled.get((3, 1)), Some(14));
+        assert_eq!(tiled.get((5, 13)), Some(624));
+        assert_eq!(tiled.get((6, 0)), None);
+        assert_eq!(tiled.adj4((5, 13)).count(), 2);
+
+        let flat = tiled.to_grid();
+        assert_eq!(flat.dims(), (6, 14));
+        assert_eq!(
+            flat.row(2),
+            &[11, 12, 111, 112, 211, 212, 311, 312, 411, 412, 511, 512, 611, 612]
+        );
+
+        let single = TiledGrid::new(&grid, (1, 1), |&v, _| v);
+        assert_eq!(single.to_grid(), grid);
+    }
 }
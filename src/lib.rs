@@ -1,8 +1,15 @@
 #[macro_use]
 pub mod utils;
 pub mod _2d_int;
+pub mod bits;
+pub mod bitvector;
+pub mod days;
 pub mod grid;
 pub mod nom_utils;
+pub mod num;
+pub mod parse;
+pub mod search;
+pub mod solver;
 pub mod vectorized;
 
 #[cfg(test)]
@@ -1,16 +1,329 @@
-use core::ops::RangeFrom;
-use nom::combinator::map;
+use core::ops::{RangeFrom, RangeTo};
+use nom::error::ErrorKind;
 use nom::{
     bits::complete::take as take_bits, error::ParseError, IResult, InputIter, InputLength, Slice,
 };
 
-pub fn take_rem<I, E: ParseError<(I, usize)>>(
-) -> impl Fn((I, usize)) -> IResult<(I, usize), (u8, usize), E>
+pub type BitInput<I> = (I, usize);
+pub type Prefixed<O> = (usize, Vec<O>);
+
+// Defines the parsers that exist in both complete and streaming forms, from
+// the matching nom `take`.
+macro_rules! bit_parsers {
+    ($take:path) => {
+        pub fn take_rem<I, E: nom::error::ParseError<$crate::nom_utils::BitInput<I>>>(
+        ) -> impl Fn($crate::nom_utils::BitInput<I>) -> nom::IResult<$crate::nom_utils::BitInput<I>, (u8, usize), E>
+        where
+            I: nom::Slice<core::ops::RangeFrom<usize>> + nom::InputIter<Item = u8> + nom::InputLength,
+        {
+            |(input, bit_offset): $crate::nom_utils::BitInput<I>| {
+                let bitlen = (8usize - bit_offset) % 8usize;
+                nom::combinator::map($take(bitlen), move |bits| (bits, bitlen))((input, bit_offset))
+            }
+        }
+
+        // Fails with `ErrorKind::TooLarge`, at the start of the varint, if the
+        // value does not fit in `O`.
+        pub fn continuation_varint<I, O, E>(
+            group_bits: usize,
+        ) -> impl Fn($crate::nom_utils::BitInput<I>) -> nom::IResult<$crate::nom_utils::BitInput<I>, O, E>
+        where
+            I: Clone + nom::Slice<core::ops::RangeFrom<usize>> + nom::InputIter<Item = u8> + nom::InputLength,
+            O: $crate::nom_utils::VarintAccumulator,
+            E: nom::error::ParseError<$crate::nom_utils::BitInput<I>>,
+        {
+            assert!(group_bits <= 8, "groups are read as bytes");
+            move |start: $crate::nom_utils::BitInput<I>| {
+                let mut input = start.clone();
+                let mut result = O::default();
+                loop {
+                    let (rest, bit_continue): (_, u8) = $take(1_usize)(input)?;
+                    let (rest, group): (_, u8) = $take(group_bits)(rest)?;
+                    result = result.push_group(group, group_bits).ok_or_else(|| {
+                        nom::Err::Failure(E::from_error_kind(start.clone(), nom::error::ErrorKind::TooLarge))
+                    })?;
+                    input = rest;
+                    if bit_continue == 0 {
+                        return Ok((input, result));
+                    }
+                }
+            }
+        }
+    };
+}
+
+bit_parsers!(nom::bits::complete::take);
+
+pub fn bits_remaining<I: InputLength>((input, bit_offset): &BitInput<I>) -> usize {
+    8 * input.input_len() - bit_offset
+}
+
+pub fn consumed_bits<I, O, E, F>(
+    mut parser: F,
+) -> impl FnMut(BitInput<I>) -> IResult<BitInput<I>, (O, usize), E>
 where
-    I: Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength,
+    I: InputLength,
+    F: nom::Parser<BitInput<I>, O, E>,
 {
-    |(input, bit_offset): (I, usize)| {
-        let bitlen = (8usize - bit_offset) % 8usize;
-        map(take_bits(bitlen), move |bits| (bits, bitlen))((input, bit_offset))
+    move |input: BitInput<I>| {
+        let before = bits_remaining(&input);
+        let (rest, output) = parser.parse(input)?;
+        let bitlen = before - bits_remaining(&rest);
+        Ok((rest, (output, bitlen)))
+    }
+}
+
+// Values that a varint can be accumulated into, one group at a time.
+// Fixed-width integers return `None` instead of dropping high bits.
+pub trait VarintAccumulator: Sized + Default {
+    fn push_group(self, group: u8, group_bits: usize) -> Option<Self>;
+}
+
+macro_rules! impl_varint_accumulator {
+    ($($t:ty),*) => {
+        $(
+            impl VarintAccumulator for $t {
+                fn push_group(self, group: u8, group_bits: usize) -> Option<Self> {
+                    if self == 0 {
+                        Some(group.into())
+                    } else if (self.leading_zeros() as usize) < group_bits {
+                        None
+                    } else {
+                        Some((self << group_bits) | <$t>::from(group))
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_varint_accumulator!(u8, u16, u32, u64, u128, usize);
+
+impl VarintAccumulator for num_bigint::BigUint {
+    fn push_group(self, group: u8, group_bits: usize) -> Option<Self> {
+        Some((self << group_bits) | Self::from(group))
+    }
+}
+
+// Errors that record where they happened as the number of bits left in the
+// stream. An error from a truncated sub-stream is `bits` short of its position
+// in the whole stream, and `shifted` adds them back.
+pub trait BitOffsetError {
+    fn shifted(self, bits: usize) -> Self;
+}
+
+// The items are parsed from a sub-stream that ends with the last byte the
+// length covers, so they cannot read beyond it. An item that ends past the
+// length, or a length the items do not fill exactly, is a `LengthValue`
+// failure; an item that consumes nothing fails with `Many0`, as in `many0`.
+pub fn length_prefixed<I, O, E, F>(
+    len_bits: usize,
+    mut parser: F,
+) -> impl FnMut(BitInput<I>) -> IResult<BitInput<I>, Prefixed<O>, E>
+where
+    I: Clone + Slice<RangeFrom<usize>> + Slice<RangeTo<usize>> + InputIter<Item = u8> + InputLength,
+    E: ParseError<BitInput<I>> + BitOffsetError,
+    F: nom::Parser<BitInput<I>, O, E>,
+{
+    move |input: BitInput<I>| {
+        let (body, len): (_, usize) = take_bits(len_bits)(input.clone())?;
+        let length_error =
+            || nom::Err::Failure(E::from_error_kind(input.clone(), ErrorKind::LengthValue));
+        if len > bits_remaining(&body) {
+            return Err(length_error());
+        }
+
+        let sub_bytes = (body.1 + len).div_ceil(8);
+        let mut rest = (body.0.slice(..sub_bytes), body.1);
+        let tail = bits_remaining(&body) - bits_remaining(&rest);
+        let end = bits_remaining(&rest) - len;
+        let full_at = |sub: &BitInput<I>| (body.0.slice(sub_bytes - sub.0.input_len()..), sub.1);
+
+        let mut outputs = Vec::new();
+        while bits_remaining(&rest) > end {
+            let before = bits_remaining(&rest);
+            let (next, output) = match parser.parse(rest.clone()) {
+                Ok(parsed) => parsed,
+                // An item that only fails for lack of the bits past the end
+                // overruns the length rather than the stream.
+                Err(nom::Err::Error(_)) | Err(nom::Err::Failure(_))
+                    if parser.parse(full_at(&rest)).is_ok() =>
+                {
+                    return Err(length_error());
+                }
+                Err(e) => return Err(e.map(|e| e.shifted(tail))),
+            };
+            if bits_remaining(&next) == before {
+                return Err(nom::Err::Error(
+                    E::from_error_kind(rest, ErrorKind::Many0).shifted(tail),
+                ));
+            }
+            rest = next;
+            outputs.push(output);
+        }
+        if bits_remaining(&rest) != end {
+            return Err(length_error());
+        }
+
+        Ok((full_at(&rest), (len, outputs)))
+    }
+}
+
+pub fn count_prefixed<I, O, E, F>(
+    count_bits: usize,
+    mut parser: F,
+) -> impl FnMut(BitInput<I>) -> IResult<BitInput<I>, Prefixed<O>, E>
+where
+    I: Clone + Slice<RangeFrom<usize>> + InputIter<Item = u8> + InputLength,
+    E: ParseError<BitInput<I>>,
+    F: nom::Parser<BitInput<I>, O, E>,
+{
+    move |input: BitInput<I>| {
+        let (mut rest, count): (_, usize) = take_bits(count_bits)(input)?;
+
+        // `count` comes from the input, so it does not size the allocation.
+        let mut outputs = Vec::new();
+        for _ in 0..count {
+            let (next, output) = parser.parse(rest)?;
+            rest = next;
+            outputs.push(output);
+        }
+
+        Ok((rest, (count, outputs)))
+    }
+}
+
+// The streaming variants return `Incomplete` rather than an error when the
+// input runs out, so a caller can read more and retry.
+pub mod streaming {
+    bit_parsers!(nom::bits::streaming::take);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bits::BitsError;
+
+    type TestResult<'a, O> = IResult<BitInput<&'a [u8]>, O, nom::error::Error<BitInput<&'a [u8]>>>;
+
+    #[test]
+    fn test_consumed_bits() {
+        let input: &[u8] = &[0b1011_0110, 0xFF];
+        let result: TestResult<_> = consumed_bits(take_bits::<_, u8, _, _>(5_usize))((input, 2));
+        assert_eq!(result, Ok(((input, 7), (0b11011, 5))));
+    }
+
+    #[test]
+    fn test_continuation_varint() {
+        // groups 0111, 1110, 0101 with continuation bits 1, 1, 0
+        let input: &[u8] = &[0b1011_1111, 0b1000_1010, 0b0000_0000];
+        let result: TestResult<u64> = continuation_varint(4)((input, 0));
+        assert_eq!(result, Ok(((&input[1..], 7), 0x7E5)));
+
+        let result: TestResult<u64> = continuation_varint(4)((&input[..1], 0));
+        assert!(result.is_err());
+    }
+
+    // Packs 4-bit groups with their continuation bits, zero-padded to bytes.
+    fn varint(groups: &[u8]) -> Vec<u8> {
+        let bits: String = groups
+            .iter()
+            .enumerate()
+            .map(|(i, group)| format!("{}{:04b}", (i + 1 < groups.len()) as u8, group))
+            .collect();
+        bits.as_bytes()
+            .chunks(8)
+            .map(|byte| {
+                let byte = core::str::from_utf8(byte).unwrap();
+                u8::from_str_radix(&format!("{:0<8}", byte), 2).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_continuation_varint_overflow() {
+        // 16 groups fill a u64 exactly; a 17th overflows it
+        let input = varint(&[0xF; 16]);
+        let result: TestResult<u64> = continuation_varint(4)((&input[..], 0));
+        assert_eq!(result.map(|(_, value)| value), Ok(u64::MAX));
+
+        let input = varint(&[0xF; 17]);
+        let result: TestResult<u64> = continuation_varint(4)((&input[..], 0));
+        assert_eq!(
+            result,
+            Err(nom::Err::Failure(nom::error::Error::new(
+                (&input[..], 0),
+                ErrorKind::TooLarge
+            )))
+        );
+        let result: TestResult<num_bigint::BigUint> = continuation_varint(4)((&input[..], 0));
+        assert_eq!(result.unwrap().1.bits(), 68);
+
+        // leading zero groups take no room
+        let input = varint(&[0, 0, 1, 1]);
+        let result: TestResult<u8> = continuation_varint(4)((&input[..], 0));
+        assert_eq!(result.map(|(_, value)| value), Ok(0x11));
+        let input = varint(&[1, 1, 1]);
+        let result: TestResult<u8> = continuation_varint(4)((&input[..], 0));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_length_prefixed() {
+        // Offsets are bits left in the whole input.
+        type BitsResult<'a, O> = IResult<BitInput<&'a [u8]>, O, BitsError>;
+
+        // 4-bit length of 6, then three 2-bit items
+        let input: &[u8] = &[0b0110_0110, 0b1100_0000];
+        let result: BitsResult<Prefixed<u8>> = length_prefixed(4, take_bits(2_usize))((input, 0));
+        assert_eq!(result, Ok(((&input[1..], 2), (6, vec![1, 2, 3]))));
+
+        // a length of 5 cannot be split into 2-bit items
+        let input: &[u8] = &[0b0101_0110, 0b1100_0000];
+        let result: BitsResult<Prefixed<u8>> = length_prefixed(4, take_bits(2_usize))((input, 0));
+        assert_eq!(
+            result,
+            Err(nom::Err::Failure(BitsError::LengthOverflow { offset: 16 }))
+        );
+
+        // a 6-bit item overruns the 4 bits the length covers
+        let input: &[u8] = &[0b0100_1111, 0b1100_0000];
+        let result: BitsResult<Prefixed<u8>> = length_prefixed(4, take_bits(6_usize))((input, 0));
+        assert_eq!(
+            result,
+            Err(nom::Err::Failure(BitsError::LengthOverflow { offset: 16 }))
+        );
+
+        // an item that fails within the length keeps its own error, moved
+        // onto the whole input
+        let input: &[u8] = &[0b0110_1111, 0b1100_0000];
+        let mut flag = |input| match take_bits::<_, u8, _, _>(2_usize)(input)? {
+            (_, 0b11) => Err(nom::Err::Failure(BitsError::Truncated {
+                offset: bits_remaining(&input),
+            })),
+            parsed => Ok(parsed),
+        };
+        let result: BitsResult<Prefixed<u8>> = length_prefixed(4, &mut flag)((input, 0));
+        assert_eq!(
+            result,
+            Err(nom::Err::Failure(BitsError::Truncated { offset: 12 }))
+        );
+
+        // an item that consumes nothing would never reach the end
+        let input: &[u8] = &[0b0010_0000, 0];
+        let result: BitsResult<Prefixed<u8>> =
+            length_prefixed(4, nom::combinator::success(0))((input, 0));
+        assert_eq!(
+            result,
+            Err(nom::Err::Error(BitsError::Truncated { offset: 12 }))
+        );
+    }
+
+    #[test]
+    fn test_count_prefixed() {
+        // 3-bit count of 2, then two 3-bit items
+        let input: &[u8] = &[0b0101_0111, 0b0000_0000];
+        let result: TestResult<Prefixed<u8>> = count_prefixed(3, take_bits(3_usize))((input, 0));
+        assert_eq!(result, Ok(((&input[1..], 1), (2, vec![0b101, 0b110]))));
     }
 }
@@ -0,0 +1,360 @@
+use core::fmt;
+use core::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+// Overflow-checked arithmetic on the primitive integers.
+pub trait CheckedArith: Copy {
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+pub trait Integer:
+    CheckedArith
+    + Ord
+    + fmt::Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const TWO: Self;
+
+    fn abs(self) -> Self;
+    fn checked_abs(self) -> Option<Self>;
+    fn rem_euclid(self, m: Self) -> Self;
+    fn isqrt(self) -> Self;
+}
+
+pub trait Signed: Integer + Neg<Output = Self> {}
+
+macro_rules! impl_integer {
+    ($abs:expr, $checked_abs:expr; $($t:ty),*) => {
+        $(
+            impl CheckedArith for $t {
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+            }
+
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const TWO: Self = 2;
+
+                fn abs(self) -> Self {
+                    $abs(self)
+                }
+
+                fn checked_abs(self) -> Option<Self> {
+                    $checked_abs(self)
+                }
+
+                fn rem_euclid(self, m: Self) -> Self {
+                    <$t>::rem_euclid(self, m)
+                }
+
+                fn isqrt(self) -> Self {
+                    <$t>::isqrt(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(|n: Self| n.abs(), |n: Self| n.checked_abs(); i8, i16, i32, i64, i128, isize);
+impl_integer!(|n: Self| n, Some; u8, u16, u32, u64, u128, usize);
+
+impl Signed for i8 {}
+impl Signed for i16 {}
+impl Signed for i32 {}
+impl Signed for i64 {}
+impl Signed for i128 {}
+impl Signed for isize {}
+
+// Always non-negative; `gcd(0, 0) == 0`. Panics if the result does not fit,
+// which only happens for `gcd(T::MIN, 0)` and `gcd(T::MIN, T::MIN)`.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    checked_gcd(a, b).expect("gcd does not fit in the integer type")
+}
+
+pub fn checked_gcd<T: Integer>(mut a: T, mut b: T) -> Option<T> {
+    while a != T::ZERO {
+        b = b % a;
+        core::mem::swap(&mut a, &mut b);
+    }
+    b.checked_abs()
+}
+
+// Always non-negative. Panics if the result does not fit; see `checked_lcm`.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    checked_lcm(a, b).expect("lcm does not fit in the integer type")
+}
+
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    (a / checked_gcd(a, b)?).checked_mul(b)?.checked_abs()
+}
+
+// `(g, x, y)` with `a * x + b * y == g == gcd(a, b)`.
+pub fn extended_gcd<T: Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (T::ONE, T::ZERO);
+    let (mut y0, mut y1) = (T::ZERO, T::ONE);
+    while r1 != T::ZERO {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+
+    if r0 < T::ZERO {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+// The inverse of `a` modulo `m`, in `0..m`; `None` unless they are coprime or
+// if `m` is not positive.
+pub fn mod_inverse<T: Signed>(a: T, m: T) -> Option<T> {
+    if m <= T::ZERO {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == T::ONE).then(|| x.rem_euclid(m))
+}
+
+// Both arguments must already be reduced into `0..m`.
+fn add_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+// Multiplication by doubling, so no intermediate value exceeds `m`.
+fn mul_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    let (mut a, mut b) = (a.rem_euclid(m), b.rem_euclid(m));
+    let mut result = T::ZERO;
+    while b != T::ZERO {
+        if b % T::TWO == T::ONE {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b = b / T::TWO;
+    }
+    result
+}
+
+// Binary exponentiation, generalised to applying `base` to a `state` `exp`
+// times: `square` must produce a base equivalent to applying `base` twice.
+pub fn pow_apply<S, B, A, Q>(
+    mut state: S,
+    mut base: B,
+    mut exp: u64,
+    mut apply: A,
+    mut square: Q,
+) -> S
+where
+    A: FnMut(S, &B) -> S,
+    Q: FnMut(&B) -> B,
+{
+    while exp > 0 {
+        if exp & 1 == 1 {
+            state = apply(state, &base);
+        }
+        exp >>= 1;
+        if exp > 0 {
+            base = square(&base);
+        }
+    }
+    state
+}
+
+// `base` to the `exp` modulo `m`, in `0..m`; `None` if `m` is not positive.
+pub fn mod_pow<T: Integer>(base: T, exp: u64, m: T) -> Option<T> {
+    if m <= T::ZERO {
+        return None;
+    }
+    Some(pow_apply(
+        T::ONE.rem_euclid(m),
+        base.rem_euclid(m),
+        exp,
+        |acc, &b| mul_mod(acc, b, m),
+        |&b| mul_mod(b, b, m),
+    ))
+}
+
+// Solves `x ≡ r (mod m)` for every `(r, m)`, returning `(x, lcm of the m)` with
+// `x` in `0..lcm`. The moduli need not be coprime, but must be positive; `None`
+// if one is not, if the congruences are inconsistent or if the combined
+// modulus overflows.
+pub fn crt<T: Signed>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    congruences
+        .iter()
+        .try_fold((T::ZERO, T::ONE), |(r1, m1), &(r2, m2)| {
+            if m2 <= T::ZERO {
+                return None;
+            }
+            let r2 = r2.rem_euclid(m2);
+            let g = gcd(m1, m2);
+            let diff = r2 - r1;
+            if diff % g != T::ZERO {
+                return None;
+            }
+
+            let step = m2 / g;
+            let modulus = (m1 / g).checked_mul(m2)?;
+            let inverse = mod_inverse(m1 / g, step)?;
+            let k = mul_mod(diff / g, inverse, step);
+            Some((add_mod(r1, m1 * k, modulus), modulus))
+        })
+}
+
+// `0 + 1 + ... + n`, without overflowing before the final result does.
+pub fn triangular<T: Integer>(n: T) -> T {
+    if n % T::TWO == T::ZERO {
+        n / T::TWO * (n + T::ONE)
+    } else {
+        (n + T::ONE) / T::TWO * n
+    }
+}
+
+// The `n`th `sides`-gonal number: 1, sides, ... for n = 1, 2, ....
+pub fn figurate<T: Integer>(sides: T, n: T) -> T {
+    if n == T::ZERO {
+        return T::ZERO;
+    }
+    n + (sides - T::TWO) * triangular(n - T::ONE)
+}
+
+// The largest `r` with `r * r <= n`; panics if `n` is negative.
+pub fn isqrt<T: Integer>(n: T) -> T {
+    n.isqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(12i8, -18), 6);
+        assert_eq!(gcd(0u32, 7), 7);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(lcm(4u64, 6), 12);
+        assert_eq!(lcm(-4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+
+        assert_eq!(gcd(i32::MIN, 6), 2);
+        assert_eq!(checked_gcd(i32::MIN, 0), None);
+        assert_eq!(checked_gcd(i32::MIN, i32::MIN), None);
+        assert_eq!(checked_lcm(1 << 20, 3 << 20), Some(3 << 20));
+        assert_eq!(checked_lcm(1 << 20, (1 << 20) - 1), None);
+        assert_eq!(checked_lcm(u64::MAX, 2), None);
+        assert_eq!(checked_lcm(i8::MIN, 1), None);
+        assert_eq!(checked_lcm(i8::MIN, 2), None);
+        assert_eq!(checked_lcm(-64i8, 2), Some(64));
+    }
+
+    #[test]
+    #[should_panic(expected = "lcm does not fit")]
+    fn test_lcm_overflow_panics() {
+        lcm(u32::MAX, 2);
+    }
+
+    #[test]
+    fn test_extended_gcd_and_inverse() {
+        for &(a, b) in &[(240, 46), (-240, 46), (46, -240), (7, 0), (0, -7)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_inverse(3, 0), None);
+        assert_eq!(mod_inverse(3, -11), None);
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(2, 10, 1000), Some(24));
+        assert_eq!(mod_pow(-2, 3, 5), Some(2));
+        assert_eq!(mod_pow(7u32, 0, 13), Some(1));
+        assert_eq!(mod_pow(7u32, 5, 1), Some(0));
+        assert_eq!(mod_pow(7u32, 5, 0), None);
+        assert_eq!(mod_pow(7, 5, -3), None);
+        // the modulus is too large for a plain `u64` product
+        let p = 18_446_744_073_709_551_557u64;
+        assert_eq!(mod_pow(3, p - 1, p), Some(1));
+        assert_eq!(
+            pow_apply(
+                String::new(),
+                "ab".to_string(),
+                5,
+                |s, b| s + b,
+                |b| b.repeat(2)
+            )
+            .len(),
+            10
+        );
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt::<i64>(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(1, i64::MAX), (0, i64::MAX - 1)]), None);
+        assert_eq!(crt(&[(2, 3), (0, 0)]), None);
+        assert_eq!(crt(&[(2, -3)]), None);
+    }
+
+    #[test]
+    fn test_figurate() {
+        assert_eq!(
+            (0..6).map(triangular).collect::<Vec<u8>>(),
+            vec![0, 1, 3, 6, 10, 15]
+        );
+        assert_eq!(triangular(92_681u32), 4_294_930_221);
+        assert_eq!(
+            (0..5).map(|n| figurate(4, n)).collect::<Vec<_>>(),
+            vec![0, 1, 4, 9, 16]
+        );
+        assert_eq!(
+            (1..5).map(|n| figurate(5u64, n)).collect::<Vec<_>>(),
+            vec![1, 5, 12, 22]
+        );
+        assert_eq!(figurate(3, 7), triangular(7));
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(
+            (0..10).map(isqrt).collect::<Vec<u32>>(),
+            vec![0, 1, 1, 1, 2, 2, 2, 2, 2, 3]
+        );
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(isqrt(1_000_000i64), 1000);
+    }
+}
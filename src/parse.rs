@@ -0,0 +1,348 @@
+use crate::_2d_int::{LineSegment, Point};
+use crate::grid::Grid;
+
+use core::fmt;
+use core::str::FromStr;
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, digit1, line_ending, one_of, satisfy, space0};
+use nom::combinator::{all_consuming, cut, eof, map, map_res, not, opt, recognize};
+use nom::error::{context, VerboseError, VerboseErrorKind};
+use nom::multi::{count, many1, separated_list1};
+use nom::sequence::{pair, separated_pair, terminated, tuple};
+use nom::Parser;
+
+pub type PResult<'a, O> = nom::IResult<&'a str, O, VerboseError<&'a str>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    // `rest` must be a suffix of `input`; the error points at its first character.
+    pub fn at(input: &str, rest: &str, expected: impl Into<String>) -> Self {
+        let offset = input.len() - rest.len();
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        Self {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    fn from_verbose(input: &str, err: VerboseError<&str>) -> Self {
+        let context = err.errors.iter().find_map(|(rest, kind)| match kind {
+            VerboseErrorKind::Context(ctx) => Some((*rest, ctx.to_string())),
+            _ => None,
+        });
+        let (rest, expected) = context
+            .or_else(|| {
+                err.errors.first().map(|(rest, kind)| {
+                    let expected = match kind {
+                        VerboseErrorKind::Char(c) => format!("{:?}", c),
+                        VerboseErrorKind::Nom(nom::error::ErrorKind::Eof) => {
+                            "end of input".to_string()
+                        }
+                        VerboseErrorKind::Nom(kind) => kind.description().to_lowercase(),
+                        VerboseErrorKind::Context(ctx) => ctx.to_string(),
+                    };
+                    (*rest, expected)
+                })
+            })
+            .unwrap_or(("", String::new()));
+
+        Self::at(input, rest, expected)
+    }
+}
+
+// The alternate form (`{:#}`) adds the offending line with a caret under the
+// reported column.
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )?;
+
+        if f.alternate() {
+            let gutter = " ".repeat(self.line.to_string().len());
+            write!(
+                f,
+                "\n{} |\n{} | {}\n{} | {:>col$}",
+                gutter,
+                self.line,
+                self.snippet,
+                gutter,
+                "^",
+                col = self.column
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub fn finish<'a, O, P>(input: &'a str, parser: P) -> Result<O, ParseError>
+where
+    P: Parser<&'a str, O, VerboseError<&'a str>>,
+{
+    let trimmed = input.trim_end();
+    match all_consuming(parser).parse(trimmed) {
+        Ok((_, output)) => Ok(output),
+        Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => {
+            Err(ParseError::from_verbose(trimmed, e))
+        }
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::at(trimmed, "", "more input")),
+    }
+}
+
+pub fn integer<'a, T: FromStr>(input: &'a str) -> PResult<'a, T> {
+    context(
+        "integer",
+        map_res(recognize(pair(opt(one_of("+-")), digit1)), T::from_str),
+    )(input)
+}
+
+// Like `separated_list1`, but once a separator has been consumed the next item
+// is mandatory, so a malformed item is reported where it starts rather than as
+// unexpected trailing input.
+fn separated_cut<'a, O, S, SO, P>(
+    mut sep: S,
+    mut item: P,
+) -> impl FnMut(&'a str) -> PResult<'a, Vec<O>>
+where
+    S: Parser<&'a str, SO, VerboseError<&'a str>>,
+    P: Parser<&'a str, O, VerboseError<&'a str>>,
+{
+    move |input| {
+        let (mut input, first) = item.parse(input)?;
+        let mut items = vec![first];
+        while let Ok((rest, _)) = sep.parse(input) {
+            let (rest, next) = item.parse(rest).map_err(|e| match e {
+                nom::Err::Error(e) => nom::Err::Failure(e),
+                e => e,
+            })?;
+            items.push(next);
+            input = rest;
+        }
+        Ok((input, items))
+    }
+}
+
+pub fn comma_separated<'a, O, P>(item: P) -> impl FnMut(&'a str) -> PResult<'a, Vec<O>>
+where
+    P: Parser<&'a str, O, VerboseError<&'a str>>,
+{
+    separated_cut(char(','), item)
+}
+
+fn line_break(input: &str) -> PResult<'_, ()> {
+    map(
+        pair(line_ending, not(pair(space0, alt((line_ending, eof))))),
+        |_| (),
+    )(input)
+}
+
+pub fn lines<'a, O, P>(item: P) -> impl FnMut(&'a str) -> PResult<'a, Vec<O>>
+where
+    P: Parser<&'a str, O, VerboseError<&'a str>>,
+{
+    separated_cut(line_break, item)
+}
+
+pub fn blank_line(input: &str) -> PResult<'_, ()> {
+    context(
+        "blank line",
+        map(pair(line_ending, terminated(space0, line_ending)), |_| ()),
+    )(input)
+}
+
+pub fn sections<'a, O, P>(section: P) -> impl FnMut(&'a str) -> PResult<'a, Vec<O>>
+where
+    P: Parser<&'a str, O, VerboseError<&'a str>>,
+{
+    separated_list1(blank_line, section)
+}
+
+// Every row after the first must have the same number of digits; a row that
+// does not is reported where it stops matching the first.
+pub fn digit_grid(input: &str) -> PResult<'_, Grid<u32>> {
+    const SAME_WIDTH: &str = "row as long as the first";
+    let digit = || {
+        map(satisfy(|c| c.is_ascii_digit()), |c| {
+            c.to_digit(10).expect("checked ascii digit")
+        })
+    };
+    let (mut input, first) = many1(digit())(input)?;
+    let width = first.len();
+    let mut rows = vec![first];
+    while let Ok((rest, _)) = line_break(input) {
+        let (rest, row) = cut(terminated(
+            count(context(SAME_WIDTH, digit()), width),
+            context(SAME_WIDTH, not(digit())),
+        ))(rest)?;
+        rows.push(row);
+        input = rest;
+    }
+
+    let grid = Grid::from_rows(rows).expect("rows checked to be of equal length");
+    Ok((input, grid))
+}
+
+pub fn edge<'a, O, P>(node: P) -> impl FnMut(&'a str) -> PResult<'a, (O, O)>
+where
+    P: Parser<&'a str, O, VerboseError<&'a str>> + Clone,
+{
+    move |input| separated_pair(node.clone(), char('-'), node.clone())(input)
+}
+
+pub fn point<'a, T: FromStr>(input: &'a str) -> PResult<'a, Point<T>> {
+    let comma = tuple((space0, char(','), space0));
+    map(separated_pair(integer, comma, integer), |(x, y)| Point {
+        x,
+        y,
+    })(input)
+}
+
+pub fn segment<'a, T: FromStr>(input: &'a str) -> PResult<'a, LineSegment<T>> {
+    let arrow = tuple((space0, tag("->"), space0));
+    map(separated_pair(point, arrow, point), |(p0, p1)| {
+        LineSegment { p0, p1 }
+    })(input)
+}
+
+pub fn rule<'a, K, V, PK, PV>(key: PK, value: PV) -> impl FnMut(&'a str) -> PResult<'a, (K, V)>
+where
+    PK: Parser<&'a str, K, VerboseError<&'a str>>,
+    PV: Parser<&'a str, V, VerboseError<&'a str>>,
+{
+    separated_pair(key, tag(" -> "), value)
+}
+
+pub fn word(input: &str) -> PResult<'_, &str> {
+    context(
+        "word",
+        recognize(many1(alt((satisfy(|c| c.is_alphanumeric()), char('_'))))),
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::character::complete::alpha1;
+
+    #[test]
+    fn test_comma_separated() {
+        assert_eq!(
+            finish("3,4,-3,+4\n", comma_separated(integer::<i32>)),
+            Ok(vec![3, 4, -3, 4])
+        );
+        assert_eq!(
+            finish("3,4,x", comma_separated(integer::<i32>)),
+            Err(ParseError {
+                line: 1,
+                column: 5,
+                snippet: "3,4,x".to_string(),
+                expected: "integer".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_digit_grid() {
+        let grid = finish("123\n456\n", digit_grid).unwrap();
+        assert_eq!(grid, Grid::from([[1, 2, 3], [4, 5, 6]]));
+
+        let err = finish("123\n456\n78\n", digit_grid).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 3));
+        assert_eq!(err.expected, "row as long as the first");
+
+        let err = finish("123\n4567\n", digit_grid).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+        assert_eq!(err.expected, "row as long as the first");
+
+        let err = finish("123\n4x6\n", digit_grid).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = "1,2\n\n3,4\n5,6\n\n7,8\n";
+        assert_eq!(
+            finish(input, sections(lines(comma_separated(integer::<u8>)))),
+            Ok(vec![
+                vec![vec![1, 2]],
+                vec![vec![3, 4], vec![5, 6]],
+                vec![vec![7, 8]]
+            ])
+        );
+
+        let parser = separated_pair(integer::<u8>, blank_line, integer::<u8>);
+        let err = finish("1\n2\n", parser).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 2));
+        assert_eq!(err.expected, "blank line");
+    }
+
+    #[test]
+    fn test_edges_and_rules() {
+        assert_eq!(
+            finish("start-A\nb-end", lines(edge(word))),
+            Ok(vec![("start", "A"), ("b", "end")])
+        );
+        assert_eq!(
+            finish("CH -> B\nHH -> N", lines(rule(alpha1, alpha1))),
+            Ok(vec![("CH", "B"), ("HH", "N")])
+        );
+
+        let err = finish("CH -> B\nHH => N", lines(rule(alpha1, alpha1))).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+
+    #[test]
+    fn test_segments() {
+        assert_eq!(
+            finish("0,9 -> 5,9\n8,0 -> 0,8\n", lines(segment::<i32>)),
+            Ok(vec![
+                LineSegment::new((0, 9), (5, 9)),
+                LineSegment::new((8, 0), (0, 8))
+            ])
+        );
+
+        let err = finish("0,9 -> 5,9\n8,0 -> 0,x\n", lines(segment::<i32>)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 10));
+        assert_eq!(err.expected, "integer");
+        assert_eq!(err.snippet, "8,0 -> 0,x");
+        assert_eq!(err.to_string(), "line 2, column 10: expected integer");
+    }
+
+    #[test]
+    fn test_diagnostic() {
+        let input = "1,2\n\u{e9}\u{e9},3\n";
+        let err = finish(input, lines(point::<i32>)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(
+            format!("{:#}", err),
+            "line 2, column 1: expected integer\n  |\n2 | \u{e9}\u{e9},3\n  | ^"
+        );
+
+        let err = ParseError::at(input, &input[10..], "digit");
+        assert_eq!((err.line, err.column), (2, 5));
+        assert_eq!(
+            format!("{:#}", err),
+            "line 2, column 5: expected digit\n  |\n2 | \u{e9}\u{e9},3\n  |     ^"
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use core::cmp::Reverse;
+use core::hash::Hash;
+use core::ops::Add;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub cost: C,
+    pub nodes: Vec<N>,
+    pub expanded: usize,
+}
+
+pub fn dijkstra<N, C, FN, IN, FG>(start: N, neighbors: FN, is_goal: FG) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FG: FnMut(&N) -> bool,
+{
+    astar(start, neighbors, |_| C::default(), is_goal)
+}
+
+pub fn astar<N, C, FN, IN, FH, FG>(
+    start: N,
+    mut neighbors: FN,
+    mut heuristic: FH,
+    mut is_goal: FG,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FG: FnMut(&N) -> bool,
+{
+    // Nodes are interned so that the queue only has to order indices, not `N`.
+    let mut indices = HashMap::new();
+    let mut nodes = vec![start.clone()];
+    let mut parents = vec![0];
+    let mut costs = vec![C::default()];
+    indices.insert(start.clone(), 0);
+
+    let mut expanded = 0;
+    let mut queue = BinaryHeap::new();
+    queue.push((Reverse(heuristic(&start)), Reverse(C::default()), 0));
+
+    while let Some((_, Reverse(cost), index)) = queue.pop() {
+        if cost > costs[index] {
+            continue;
+        }
+        expanded += 1;
+        if is_goal(&nodes[index]) {
+            let mut path = vec![nodes[index].clone()];
+            let mut i = index;
+            while i != 0 {
+                i = parents[i];
+                path.push(nodes[i].clone());
+            }
+            path.reverse();
+            return Some(Path {
+                cost,
+                nodes: path,
+                expanded,
+            });
+        }
+
+        for (next, step_cost) in neighbors(&nodes[index]) {
+            let next_cost = cost + step_cost;
+            let next_index = match indices.entry(next) {
+                Entry::Vacant(entry) => {
+                    let next_index = nodes.len();
+                    nodes.push(entry.key().clone());
+                    parents.push(index);
+                    costs.push(next_cost);
+                    entry.insert(next_index);
+                    next_index
+                }
+                Entry::Occupied(entry) => {
+                    let next_index = *entry.get();
+                    if next_cost >= costs[next_index] {
+                        continue;
+                    }
+                    parents[next_index] = index;
+                    costs[next_index] = next_cost;
+                    next_index
+                }
+            };
+            let estimate = next_cost + heuristic(&nodes[next_index]);
+            queue.push((Reverse(estimate), Reverse(next_cost), next_index));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_2d_int::Point;
+    use crate::grid::Grid;
+
+    fn grid_neighbors(
+        grid: &Grid<u32>,
+    ) -> impl '_ + FnMut(&Point<usize>) -> Vec<(Point<usize>, u32)> {
+        move |p| {
+            grid.adj4((p.y, p.x))
+                .map(|(y, x)| (Point { x, y }, grid[(y, x)]))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_dijkstra_graph() {
+        let edges: HashMap<char, Vec<(char, u32)>> = [
+            ('a', vec![('b', 7), ('c', 9), ('f', 14)]),
+            ('b', vec![('a', 7), ('c', 10), ('d', 15)]),
+            ('c', vec![('a', 9), ('b', 10), ('d', 11), ('f', 2)]),
+            ('d', vec![('b', 15), ('c', 11), ('e', 6)]),
+            ('e', vec![('d', 6), ('f', 9)]),
+            ('f', vec![('a', 14), ('c', 2), ('e', 9)]),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        let path = dijkstra('a', |n| edges[n].clone(), |&n| n == 'e').unwrap();
+        assert_eq!(path.cost, 20);
+        assert_eq!(path.nodes, vec!['a', 'c', 'f', 'e']);
+        assert_eq!(path.expanded, 5);
+
+        assert_eq!(dijkstra('a', |n| edges[n].clone(), |&n| n == 'z'), None);
+    }
+
+    #[test]
+    fn test_astar_grid() {
+        let grid = Grid::from([[1, 9, 1, 1, 1], [1, 9, 1, 9, 1], [1, 1, 1, 9, 1]]);
+        let goal = Point { x: 4, y: 2 };
+
+        let path = astar(
+            Point { x: 0, y: 0 },
+            grid_neighbors(&grid),
+            |p| p.manhattan_distance(goal) as u32,
+            |&p| p == goal,
+        )
+        .unwrap();
+        assert_eq!(path.cost, 10);
+        assert_eq!(path.nodes.first(), Some(&Point { x: 0, y: 0 }));
+        assert_eq!(path.nodes.last(), Some(&goal));
+        assert_eq!(path.nodes.len(), 11);
+
+        let baseline =
+            dijkstra(Point { x: 0, y: 0 }, grid_neighbors(&grid), |&p| p == goal).unwrap();
+        assert_eq!(baseline.cost, 10);
+        assert!(path.expanded <= baseline.expanded);
+    }
+}
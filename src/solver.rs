@@ -0,0 +1,24 @@
+use crate::utils::AnyError;
+
+use core::fmt::Display;
+
+pub trait Solver {
+    type Input;
+    type Error: Into<AnyError>;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error>;
+}
+
+pub fn solve_part1<S: Solver>(input: &str) -> Result<String, AnyError> {
+    let input = S::parse(input).map_err(Into::into)?;
+    S::part1(&input).map(|a| a.to_string()).map_err(Into::into)
+}
+
+pub fn solve_part2<S: Solver>(input: &str) -> Result<String, AnyError> {
+    let input = S::parse(input).map_err(Into::into)?;
+    S::part2(&input).map(|a| a.to_string()).map_err(Into::into)
+}
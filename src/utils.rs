@@ -1,31 +1,204 @@
+use crate::parse::ParseError;
+
+use core::fmt;
 use std::io::BufRead;
 use std::str::FromStr;
 
+pub type AnyError = Box<dyn std::error::Error>;
+
+// Numbered lines, ending after the first read error: `Lines` can yield the
+// same error forever, so reading past one might never return.
+fn numbered_lines<R: BufRead>(
+    reader: R,
+) -> impl Iterator<Item = Result<(usize, String), LineError>> {
+    let mut failed = false;
+    reader.lines().enumerate().map_while(move |(i, line)| {
+        if failed {
+            return None;
+        }
+        failed = line.is_err();
+        Some(line.map(|s| (i + 1, s)).map_err(|e| LineError {
+            line: i + 1,
+            content: String::new(),
+            source: e.into(),
+        }))
+    })
+}
+
+// Lines that fail to parse are skipped; a read error ends the iteration.
 pub fn parsing_input<R: BufRead, T: FromStr>(reader: R) -> impl Iterator<Item = T> {
-    reader
-        .lines()
-        .filter_map(|r| r.ok())
-        .filter_map(|s| s.parse::<T>().ok())
+    numbered_lines(reader)
+        .map_while(Result::ok)
+        .filter_map(|(_, s)| s.parse::<T>().ok())
 }
 
-pub struct ArrayWrapper<T, const LEN: usize>(pub [T; LEN]);
+#[derive(Debug)]
+pub struct LineError {
+    pub line: usize,
+    pub content: String,
+    pub source: AnyError,
+}
 
-impl<T, const LEN: usize> std::iter::FromIterator<T> for ArrayWrapper<T, LEN> {
-    fn from_iter<I: IntoIterator<Item = T>>(iterable: I) -> Self {
-        let mut result: [T; LEN] = unsafe { std::mem::uninitialized() };
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Each line is parsed on its own, so a `ParseError` only knows its
+        // column; patch in the line number before rendering it.
+        match self.source.downcast_ref::<ParseError>() {
+            Some(err) => fmt::Display::fmt(
+                &ParseError {
+                    line: self.line,
+                    ..err.clone()
+                },
+                f,
+            ),
+            None => write!(
+                f,
+                "line {}: {} ({:?})",
+                self.line, self.source, self.content
+            ),
+        }
+    }
+}
 
-        let mut write_len = 0;
-        for (i, item) in iterable.into_iter().enumerate() {
-            unsafe {
-                std::ptr::write(&mut result[i], item);
-            }
-            write_len += 1;
+impl std::error::Error for LineError {}
+
+fn parse_line<T>(line: usize, content: String) -> Result<T, LineError>
+where
+    T: FromStr,
+    <T as FromStr>::Err: 'static + std::error::Error,
+{
+    content.parse::<T>().map_err(|e| LineError {
+        line,
+        content,
+        source: e.into(),
+    })
+}
+
+// A read error is yielded like a bad line, but nothing is read after it.
+pub fn parsing_input_strict<R, T>(reader: R) -> impl Iterator<Item = Result<T, LineError>>
+where
+    R: BufRead,
+    T: FromStr,
+    <T as FromStr>::Err: 'static + std::error::Error,
+{
+    numbered_lines(reader).map(|line| line.and_then(|(i, s)| parse_line(i, s)))
+}
+
+#[derive(Debug)]
+pub struct ParseReport<T> {
+    pub items: Vec<T>,
+    pub skipped: Vec<LineError>,
+}
+
+// Only lines that fail to parse are skipped; a read error is returned instead
+// of a report.
+pub fn parsing_input_lenient<R, T>(reader: R) -> Result<ParseReport<T>, LineError>
+where
+    R: BufRead,
+    T: FromStr,
+    <T as FromStr>::Err: 'static + std::error::Error,
+{
+    let mut report = ParseReport {
+        items: Vec::new(),
+        skipped: Vec::new(),
+    };
+    for line in numbered_lines(reader) {
+        let (i, s) = line?;
+        match parse_line(i, s) {
+            Ok(item) => report.items.push(item),
+            Err(err) => report.skipped.push(err),
         }
-        if write_len != LEN {
-            panic!("wrong number of entries found; expected {:?}", LEN);
+    }
+
+    Ok(report)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ArrayLengthError {
+    TooShort { expected: usize, found: usize },
+    TooLong { expected: usize },
+}
+
+impl fmt::Display for ArrayLengthError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::TooShort { expected, found } => write!(
+                f,
+                "wrong number of entries found; expected {}, found {}",
+                expected, found
+            ),
+            Self::TooLong { expected } => write!(
+                f,
+                "wrong number of entries found; expected {}, found more",
+                expected
+            ),
         }
+    }
+}
+
+impl std::error::Error for ArrayLengthError {}
+
+// Items are staged as `Option`s so that every partially-built array is a valid
+// value; if the iterator panics or comes up short, whatever was already taken
+// from it is dropped normally.
+pub fn try_array_from_iter<T, I, const LEN: usize>(
+    iterable: I,
+) -> Result<[T; LEN], ArrayLengthError>
+where
+    I: IntoIterator<Item = T>,
+{
+    let mut iter = iterable.into_iter().fuse();
+    let items: [Option<T>; LEN] = core::array::from_fn(|_| iter.next());
+
+    let found = items.iter().filter(|item| item.is_some()).count();
+    if found < LEN {
+        return Err(ArrayLengthError::TooShort {
+            expected: LEN,
+            found,
+        });
+    }
+    if iter.next().is_some() {
+        return Err(ArrayLengthError::TooLong { expected: LEN });
+    }
+
+    Ok(items.map(|item| item.expect("all items checked present")))
+}
+
+pub trait TryFromIterator<T>: Sized {
+    type Error;
+
+    fn try_from_iter<I: IntoIterator<Item = T>>(iterable: I) -> Result<Self, Self::Error>;
+}
+
+pub trait TryCollect: Iterator + Sized {
+    fn try_collect_into<B: TryFromIterator<Self::Item>>(self) -> Result<B, B::Error> {
+        B::try_from_iter(self)
+    }
+}
+
+impl<I: Iterator> TryCollect for I {}
+
+impl<T, const LEN: usize> TryFromIterator<T> for [T; LEN] {
+    type Error = ArrayLengthError;
+
+    fn try_from_iter<I: IntoIterator<Item = T>>(iterable: I) -> Result<Self, Self::Error> {
+        try_array_from_iter(iterable)
+    }
+}
+
+pub struct ArrayWrapper<T, const LEN: usize>(pub [T; LEN]);
+
+impl<T, const LEN: usize> TryFromIterator<T> for ArrayWrapper<T, LEN> {
+    type Error = ArrayLengthError;
+
+    fn try_from_iter<I: IntoIterator<Item = T>>(iterable: I) -> Result<Self, Self::Error> {
+        try_array_from_iter(iterable).map(Self)
+    }
+}
 
-        Self(result)
+impl<T, const LEN: usize> std::iter::FromIterator<T> for ArrayWrapper<T, LEN> {
+    fn from_iter<I: IntoIterator<Item = T>>(iterable: I) -> Self {
+        Self::try_from_iter(iterable).unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
@@ -48,3 +221,175 @@ pub fn n_min<T: core::cmp::Ord, I: Iterator<Item = T>>(n: usize, mut iter: I) ->
 
     buffer.into_sorted_vec()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    // Yields `data`, then fails on every read after it.
+    struct FailingReader<'a> {
+        data: &'a [u8],
+    }
+
+    impl std::io::Read for FailingReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.data.is_empty() {
+                return Err(std::io::Error::other("disk on fire"));
+            }
+            let len = buf.len().min(self.data.len());
+            buf[..len].copy_from_slice(&self.data[..len]);
+            self.data = &self.data[len..];
+            Ok(len)
+        }
+    }
+
+    fn failing_input() -> std::io::BufReader<FailingReader<'static>> {
+        std::io::BufReader::new(FailingReader {
+            data: b"1\nx\n3\n4",
+        })
+    }
+
+    #[test]
+    fn test_parsing_stops_at_read_error() {
+        assert_eq!(
+            parsing_input::<_, i32>(failing_input()).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+
+        let results: Vec<Result<i32, LineError>> = parsing_input_strict(failing_input()).collect();
+        assert_eq!(
+            results
+                .iter()
+                .map(|r| r.as_ref().ok().copied())
+                .collect::<Vec<_>>(),
+            vec![Some(1), None, Some(3), None]
+        );
+        let err = results[3].as_ref().unwrap_err();
+        assert_eq!(
+            (err.line, err.source.to_string()),
+            (4, "disk on fire".into())
+        );
+
+        let err = parsing_input_lenient::<_, i32>(failing_input()).unwrap_err();
+        assert_eq!(err.line, 4);
+    }
+
+    struct DropCounter<'a>(&'a Cell<usize>);
+
+    impl Drop for DropCounter<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn test_try_from_iter_lengths() {
+        let words = || ["a", "b", "c"].iter().map(|s| s.to_string());
+
+        let ArrayWrapper(array) = ArrayWrapper::<String, 3>::try_from_iter(words()).unwrap();
+        assert_eq!(array, ["a", "b", "c"]);
+        assert_eq!(
+            words().try_collect_into::<[String; 4]>(),
+            Err(ArrayLengthError::TooShort {
+                expected: 4,
+                found: 3
+            })
+        );
+        assert_eq!(
+            words().try_collect_into::<[String; 2]>(),
+            Err(ArrayLengthError::TooLong { expected: 2 })
+        );
+        assert_eq!(
+            words().try_collect_into::<[String; 0]>().err(),
+            Some(ArrayLengthError::TooLong { expected: 0 })
+        );
+    }
+
+    #[test]
+    fn test_try_from_iter_drops() {
+        let drops = Cell::new(0);
+        let result = (0..2)
+            .map(|_| DropCounter(&drops))
+            .try_collect_into::<[DropCounter; 3]>();
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 2);
+
+        let drops = Cell::new(0);
+        let result = (0..4)
+            .map(|_| DropCounter(&drops))
+            .try_collect_into::<[DropCounter; 3]>();
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn test_try_from_iter_panicking_element() {
+        let drops = Cell::new(0);
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            (0..4)
+                .map(|i| {
+                    if i == 2 {
+                        panic!("element {}", i);
+                    }
+                    DropCounter(&drops)
+                })
+                .try_collect_into::<[DropCounter; 4]>()
+                .is_ok()
+        }));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "wrong number of entries found; expected 3, found 2")]
+    fn test_array_wrapper_collect_short() {
+        let _: ArrayWrapper<u8, 3> = (0..2).collect();
+    }
+
+    const INPUT: &str = "199\n200\n2x8\n210\n\n-3\n";
+
+    #[test]
+    fn test_parsing_input_strict() {
+        let results: Vec<Result<i32, LineError>> = parsing_input_strict(INPUT.as_bytes()).collect();
+        assert_eq!(results.len(), 6);
+        assert_eq!(results[1].as_ref().ok(), Some(&200));
+
+        let err = results[2].as_ref().unwrap_err();
+        assert_eq!((err.line, err.content.as_str()), (3, "2x8"));
+        assert_eq!(
+            err.to_string(),
+            "line 3: invalid digit found in string (\"2x8\")"
+        );
+        assert_eq!(
+            parsing_input_strict::<_, i32>(INPUT.as_bytes())
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap_err()
+                .line,
+            3
+        );
+    }
+
+    #[test]
+    fn test_parsing_input_lenient() {
+        let report = parsing_input_lenient::<_, i32>(INPUT.as_bytes()).unwrap();
+        assert_eq!(report.items, vec![199, 200, 210, -3]);
+        assert_eq!(
+            report.skipped.iter().map(|e| e.line).collect::<Vec<_>>(),
+            vec![3, 5]
+        );
+    }
+
+    #[test]
+    fn test_line_error_parse_error() {
+        let input = "1,2 -> 3,4\n1,2 -> 3;4\n";
+        let err = parsing_input_strict::<_, crate::_2d_int::LineSegment<i32>>(input.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "line 2, column 9: expected ','\n  |\n2 | 1,2 -> 3;4\n  |         ^"
+        );
+    }
+}
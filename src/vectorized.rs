@@ -1,97 +1,71 @@
+use crate::parse::ParseError;
+use crate::utils::{try_array_from_iter, ArrayLengthError, TryFromIterator};
+
 use std::cmp::PartialEq;
 use std::convert::From;
-use std::ops::Add;
+use std::iter::Sum;
+use std::ops::{Add, BitAnd, BitOr, BitXor, Index, IndexMut, Mul, Neg, Not, Sub};
 use std::str::FromStr;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Eq, Hash)]
 pub struct Vectorized<T, const N: usize>(pub [T; N]);
 
 impl<const N: usize> FromStr for Vectorized<bool, N> {
-    type Err = &'static str;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() != N {
-            return Err("invalid input length");
-        }
-
         let mut array = [false; N];
-        for (a, bit) in array.iter_mut().zip(s.chars()) {
-            *a = match bit {
-                '1' => true,
-                '0' => false,
-                _ => return Err("invalid character"),
+        let mut chars = s.char_indices();
+        for a in array.iter_mut() {
+            *a = match chars.next() {
+                Some((_, '1')) => true,
+                Some((_, '0')) => false,
+                Some((i, _)) => return Err(ParseError::at(s, &s[i..], "'0' or '1'")),
+                None => return Err(ParseError::at(s, "", format!("{} bits", N))),
             }
         }
 
-        Ok(Self(array))
+        match chars.next() {
+            None => Ok(Self(array)),
+            Some((i, _)) => Err(ParseError::at(s, &s[i..], "end of line")),
+        }
     }
 }
 
 impl<T: Clone, const N: usize> Clone for Vectorized<T, N> {
     fn clone(&self) -> Self {
-        let data = unsafe {
-            /*
-            // Create an uninitialized array of `MaybeUninit`. The `assume_init` is
-            // safe because the type we are claiming to have initialized here is a
-            // bunch of `MaybeUninit`s, which do not require initialization.
-            let mut data: [MaybeUninit<T>; N] = unsafe { MaybeUninit::uninit().assume_init() };
-
-            // Dropping a `MaybeUninit` does nothing. Thus using raw pointer
-            // assignment instead of `ptr::write` does not cause the old
-            // uninitialized value to be dropped. Also if there is a panic during
-            // this loop, we have a memory leak, but there is no memory safety
-            // issue.
-            for (elem, vself) in (&mut data[..]).iter_mut().zip(&self.0[..]) {
-                elem.write(vself.clone());
-            }
+        self.map(T::clone)
+    }
+}
 
-            // Everything is initialized. Transmute the array to the
-            // initialized type.
-            unsafe { transmute::<_, [T; N]>(data) }
-            */
-            let mut data: [T; N] = std::mem::uninitialized();
-            for (elem, vself) in (&mut data[..]).iter_mut().zip(&self.0[..]) {
-                std::ptr::write(elem as *mut T, vself.clone());
-            }
-            data
-        };
+impl<T: Copy, const N: usize> Copy for Vectorized<T, N> {}
+
+impl<T: Default, const N: usize> Default for Vectorized<T, N> {
+    fn default() -> Self {
+        Vectorized(core::array::from_fn(|_| T::default()))
+    }
+}
 
-        Vectorized(data)
+impl<T, const N: usize> From<[T; N]> for Vectorized<T, N> {
+    fn from(array: [T; N]) -> Self {
+        Vectorized(array)
     }
 }
 
 impl<T, const N: usize> Vectorized<T, N> {
-    pub fn map<U, F: Fn(&T) -> U>(&self, func: F) -> Vectorized<U, N> {
-        // copied from rust reference:
-        // https://doc.rust-lang.org/std/mem/union.MaybeUninit.html#initializing-an-array-element-by-element
-        let data = unsafe {
-            /*
-            // Create an uninitialized array of `MaybeUninit`. The `assume_init` is
-            // safe because the type we are claiming to have initialized here is a
-            // bunch of `MaybeUninit`s, which do not require initialization.
-            let mut data: [MaybeUninit<U>; N] = unsafe { MaybeUninit::uninit().assume_init() };
-
-            // Dropping a `MaybeUninit` does nothing. Thus using raw pointer
-            // assignment instead of `ptr::write` does not cause the old
-            // uninitialized value to be dropped. Also if there is a panic during
-            // this loop, we have a memory leak, but there is no memory safety
-            // issue.
-            for (elem, vself) in (&mut data[..]).iter_mut().zip(&self.0[..]) {
-                elem.write(func(vself));
-            }
+    pub fn splat(value: T) -> Self
+    where
+        T: Clone,
+    {
+        Vectorized(core::array::from_fn(|_| value.clone()))
+    }
 
-            // Everything is initialized. Transmute the array to the
-            // initialized type.
-            unsafe { std::mem::transmute::<_, [U; N]>(data) }
-            */
-            let mut data: [U; N] = std::mem::uninitialized();
-            for (elem, vself) in (&mut data[..]).iter_mut().zip(&self.0[..]) {
-                std::ptr::write(elem as *mut U, func(vself));
-            }
-            data
-        };
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.0.iter()
+    }
 
-        Vectorized(data)
+    pub fn map<U, F: Fn(&T) -> U>(&self, func: F) -> Vectorized<U, N> {
+        Vectorized(core::array::from_fn(|i| func(&self.0[i])))
     }
 
     pub fn combine<T2, U, F: Fn(&T, &T2) -> U>(
@@ -99,50 +73,131 @@ impl<T, const N: usize> Vectorized<T, N> {
         vec2: &Vectorized<T2, N>,
         func: F,
     ) -> Vectorized<U, N> {
-        // copied from rust reference:
-        // https://doc.rust-lang.org/std/mem/union.MaybeUninit.html#initializing-an-array-element-by-element
-        let data = unsafe {
-            /*
-            // Create an uninitialized array of `MaybeUninit`. The `assume_init` is
-            // safe because the type we are claiming to have initialized here is a
-            // bunch of `MaybeUninit`s, which do not require initialization.
-            let mut data: [MaybeUninit<U>; N] = unsafe { MaybeUninit::uninit().assume_init() };
-
-            // Dropping a `MaybeUninit` does nothing. Thus using raw pointer
-            // assignment instead of `ptr::write` does not cause the old
-            // uninitialized value to be dropped. Also if there is a panic during
-            // this loop, we have a memory leak, but there is no memory safety
-            // issue.
-            for ((elem, vself), v2) in (&mut data[..]).iter_mut().zip(&self.0[..]).zip(&vec2.0[..])
+        Vectorized(core::array::from_fn(|i| func(&self.0[i], &vec2.0[i])))
+    }
+
+    pub fn fold<B, F: FnMut(B, T) -> B>(self, init: B, func: F) -> B {
+        IntoIterator::into_iter(self.0).fold(init, func)
+    }
+
+    pub fn reduce<F: FnMut(T, T) -> T>(self, func: F) -> Option<T> {
+        IntoIterator::into_iter(self.0).reduce(func)
+    }
+
+    fn zip_with<T2, U, F: FnMut(T, T2) -> U>(
+        self,
+        vec2: Vectorized<T2, N>,
+        mut func: F,
+    ) -> Vectorized<U, N> {
+        let mut rhs = IntoIterator::into_iter(vec2.0);
+        Vectorized(
+            self.0
+                .map(|x| func(x, rhs.next().expect("vectors have equal length"))),
+        )
+    }
+}
+
+impl<T, const N: usize> Index<usize> for Vectorized<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.0[index]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for Vectorized<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.0[index]
+    }
+}
+
+impl<T, const N: usize> IntoIterator for Vectorized<T, N> {
+    type Item = T;
+    type IntoIter = core::array::IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter(self.0)
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a Vectorized<T, N> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<T, const N: usize> TryFromIterator<T> for Vectorized<T, N> {
+    type Error = ArrayLengthError;
+
+    fn try_from_iter<I: IntoIterator<Item = T>>(iterable: I) -> Result<Self, Self::Error> {
+        try_array_from_iter(iterable).map(Self)
+    }
+}
+
+// Element-wise between two vectors, or against a scalar broadcast to every
+// element.
+macro_rules! impl_binary_op {
+    ($($trait:ident, $method:ident;)*) => {
+        $(
+            impl<T, U, const N: usize> $trait for Vectorized<T, N>
+            where
+                T: $trait<Output = U>,
             {
-                elem.write(func(vself, v2));
-            }
+                type Output = Vectorized<U, N>;
 
-            // Everything is initialized. Transmute the array to the
-            // initialized type.
-            unsafe { std::mem::transmute::<_, [U; N]>(data) }
-            */
+                fn $method(self, rhs: Self) -> Self::Output {
+                    self.zip_with(rhs, T::$method)
+                }
+            }
 
-            let mut data: [U; N] = std::mem::uninitialized();
-            for ((elem, vself), v2) in (&mut data[..]).iter_mut().zip(&self.0[..]).zip(&vec2.0[..])
+            impl<T, U, const N: usize> $trait<T> for Vectorized<T, N>
+            where
+                T: $trait<Output = U> + Clone,
             {
-                std::ptr::write(elem as *mut U, func(vself, v2));
+                type Output = Vectorized<U, N>;
+
+                fn $method(self, rhs: T) -> Self::Output {
+                    Vectorized(self.0.map(|x| x.$method(rhs.clone())))
+                }
             }
-            data
-        };
+        )*
+    };
+}
 
-        Vectorized(data)
+impl_binary_op! {
+    Add, add;
+    Sub, sub;
+    Mul, mul;
+    BitAnd, bitand;
+    BitOr, bitor;
+    BitXor, bitxor;
+}
+
+impl<T: Neg, const N: usize> Neg for Vectorized<T, N> {
+    type Output = Vectorized<T::Output, N>;
+
+    fn neg(self) -> Self::Output {
+        Vectorized(self.0.map(T::neg))
     }
 }
 
-impl<T, U, const N: usize> Add for Vectorized<T, N>
+impl<T: Not, const N: usize> Not for Vectorized<T, N> {
+    type Output = Vectorized<T::Output, N>;
+
+    fn not(self) -> Self::Output {
+        Vectorized(self.0.map(T::not))
+    }
+}
+
+impl<T, const N: usize> Sum for Vectorized<T, N>
 where
-    T: Add<Output = U> + Copy,
-    U: Default + Copy,
+    T: Add<Output = T> + Default,
 {
-    type Output = Vectorized<U, N>;
-    fn add(self, rhs: Self) -> Self::Output {
-        self.combine(&rhs, |&x, &y| x + y)
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
     }
 }
 
@@ -160,3 +215,86 @@ impl<const N: usize> From<Vectorized<bool, N>> for u32 {
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            Vectorized::<bool, 4>::from_str("1011"),
+            Ok(Vectorized([true, false, true, true]))
+        );
+
+        let err = Vectorized::<bool, 4>::from_str("10x1").unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (3, "'0' or '1'"));
+        let err = Vectorized::<bool, 4>::from_str("101").unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (4, "4 bits"));
+        let err = Vectorized::<bool, 4>::from_str("10110").unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (5, "end of line"));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Vectorized([1, -2, 3]);
+        let b = Vectorized([4, 5, -6]);
+        assert_eq!(a + b, Vectorized([5, 3, -3]));
+        assert_eq!(a - b, Vectorized([-3, -7, 9]));
+        assert_eq!(a * b, Vectorized([4, -10, -18]));
+        assert_eq!(-a, Vectorized([-1, 2, -3]));
+        assert_eq!(a * 2, Vectorized([2, -4, 6]));
+        assert_eq!(a - 1, Vectorized([0, -3, 2]));
+        assert_eq!(
+            vec![a, b, Vectorized::splat(1)]
+                .into_iter()
+                .sum::<Vectorized<_, 3>>(),
+            Vectorized([6, 4, -2])
+        );
+        assert_eq!(Vectorized::<u8, 2>::default(), Vectorized([0, 0]));
+
+        assert_eq!(a.fold(0, |acc, x| acc + x), 2);
+        assert_eq!(b.reduce(i32::max), Some(5));
+        assert_eq!(Vectorized::<i32, 0>([]).reduce(i32::max), None);
+        assert_eq!(a.iter().rev().copied().collect::<Vec<_>>(), vec![3, -2, 1]);
+        assert_eq!((a[0], b[2]), (1, -6));
+    }
+
+    #[test]
+    fn test_bitwise() {
+        let a = Vectorized([true, true, false, false]);
+        let b = Vectorized([true, false, true, false]);
+        assert_eq!(a & b, Vectorized([true, false, false, false]));
+        assert_eq!(a | b, Vectorized([true, true, true, false]));
+        assert_eq!(a ^ b, Vectorized([false, true, true, false]));
+        assert_eq!(!a, Vectorized([false, false, true, true]));
+        assert_eq!(a & true, a);
+        assert_eq!(
+            Vectorized([0b1100_u8, 0b1010]) ^ 0b1111,
+            Vectorized([0b0011, 0b0101])
+        );
+
+        let mut c = a;
+        c[3] = true;
+        assert_eq!(u32::from(c), 0b1101);
+    }
+
+    #[test]
+    fn test_owned_elements() {
+        let words = Vectorized(["ab".to_string(), "c".to_string()]);
+        assert_eq!(words.clone(), words);
+        assert_eq!(words.map(String::len), Vectorized([2, 1]));
+        assert_eq!(
+            words.combine(&Vectorized([1, 0]), |s, &n| s.repeat(n)),
+            Vectorized(["ab".to_string(), String::new()])
+        );
+
+        assert_eq!(
+            Vectorized::<String, 3>::try_from_iter(words.0),
+            Err(ArrayLengthError::TooShort {
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+}